@@ -39,6 +39,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("lookup_unit_shorthand", |b| {
         b.iter(|| unit_convert::units::find_unit(black_box("Wh")))
     });
+    c.bench_function("lookup_unit_prefixed", |b| {
+        b.iter(|| unit_convert::prefix::get(black_box("kilometer")))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use unit_convert::dimension::Dimensions;
+
+const SAMPLES: usize = 100_000;
+
+macro_rules! bench_conversion {
+    ($c:expr, $from:literal, $to:literal) => {
+        let from = Dimensions::from_str($from).unwrap();
+        let to = Dimensions::from_str($to).unwrap();
+        let values = (0..SAMPLES).map(|x| x as f64).collect::<Vec<_>>();
+
+        $c.bench_function(
+            concat!("parse and convert `", $from, "` => `", $to, "`"),
+            |b| {
+                b.iter(|| {
+                    let from = Dimensions::from_str(black_box($from)).unwrap();
+                    let to = Dimensions::from_str(black_box($to)).unwrap();
+                    from.convert(&to, black_box(1.0), false).unwrap()
+                })
+            },
+        );
+
+        $c.bench_function(concat!("convert `", $from, "` => `", $to, "`"), |b| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| {
+                    for value in values.iter_mut() {
+                        *value = from.convert(&to, *value, false).unwrap();
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        let plan = from.plan(&to).unwrap();
+        $c.bench_function(concat!("plan `", $from, "` => `", $to, "`"), |b| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| plan.convert_slice(black_box(values)),
+                BatchSize::LargeInput,
+            )
+        });
+    };
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_conversion!(c, "km/h", "m/s");
+    bench_conversion!(c, "degC", "degF");
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -1,16 +1,103 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[cfg(feature = "serde")]
+use crate::catalog::ExportFormat;
+use crate::format::{Notation, Precision};
 
 #[derive(Parser, Debug)]
-#[clap(author, about, version)]
+#[clap(author, about, version, subcommand_negates_reqs = true)]
 pub struct Args {
     // == Main Input ==
-    pub input: String,
+    #[clap(required = true)]
+    pub input: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
 
     // == Arguments ==
     /// Shows the internal conversion steps between units.
     #[clap(long)]
     pub debug: bool,
+    /// Loads extra units from a definitions file, like `smoot = 1.7018 m`.
+    /// Units in `~/.config/unit_convert/units.txt` are always loaded.
+    #[clap(long, value_name = "FILE")]
+    pub defs: Vec<PathBuf>,
+    /// Imports units from a GNU units definitions file, like `definitions.units`.
+    /// Definitions that could not be imported are listed with `--debug`.
+    #[clap(long, value_name = "FILE")]
+    pub gnu_units: Vec<PathBuf>,
+    /// The atmospheric pressure gauge units like `psig` are relative to, like `83.4 kPa`.
+    /// Defaults to the standard atmosphere.
+    #[clap(long, value_name = "PRESSURE")]
+    pub atmosphere: Option<String>,
+    /// Read units as UCUM codes, like `mg/dL` or `mm[Hg]`.
+    #[clap(short, long)]
+    pub ucum: bool,
     /// Print the dimensions of the input and output units.
     #[clap(short, long)]
     pub dimensions: bool,
+    /// How to write the input and output values.
+    #[clap(short, long, value_enum, default_value_t)]
+    pub notation: Notation,
+    /// Round values to a fixed number of decimal places.
+    #[clap(long, conflicts_with_all = ["sig_figs", "match_input"])]
+    pub decimals: Option<usize>,
+    /// Round values to a number of significant figures.
+    #[clap(short, long, conflicts_with = "match_input")]
+    pub sig_figs: Option<usize>,
+    /// Round values to as many significant figures as the input value had.
+    #[clap(short, long)]
+    pub match_input: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Prints what a unit is: its unit space, aliases, description and definition in base units.
+    Info {
+        /// A unit name or alias, optionally with a metric prefix, like `km` or `ghurry`.
+        unit: String,
+    },
+    /// Lists every unit, grouped by unit space or the kind of derived unit.
+    List {
+        /// Only list units that can use metric prefixes.
+        #[clap(long, conflicts_with = "non_metric")]
+        metric: bool,
+        /// Only list units that can not use metric prefixes.
+        #[clap(long)]
+        non_metric: bool,
+        /// Only list the units added by the `wacky_units` feature.
+        #[clap(long, conflicts_with = "no_wacky")]
+        wacky: bool,
+        /// Leave out the units added by the `wacky_units` feature.
+        #[clap(long)]
+        no_wacky: bool,
+    },
+    /// Lists every unit with the same dimensions as a unit expression, and what one of the expression is in each.
+    Compatible {
+        /// A unit expression, like `kg*m/s^2` or `psi`.
+        unit: String,
+    },
+    /// Finds units whose name, aliases or description contain a word.
+    Search { query: String },
+    /// Prints every unit, derived unit, shorthand and prefix, including ones loaded with `--defs`.
+    #[cfg(feature = "serde")]
+    Export {
+        #[clap(short, long, value_enum, default_value_t)]
+        format: ExportFormat,
+    },
+}
+
+impl Args {
+    pub fn precision(&self) -> Precision {
+        if let Some(decimals) = self.decimals {
+            Precision::Decimals(decimals)
+        } else if let Some(figures) = self.sig_figs {
+            Precision::SignificantFigures(figures)
+        } else if self.match_input {
+            Precision::MatchInput
+        } else {
+            Precision::Full
+        }
+    }
 }
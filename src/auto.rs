@@ -0,0 +1,176 @@
+use clap::ValueEnum;
+
+use crate::{
+    dimension::Dimensions, input::expression::Quantity, prefix::METRIC_PREFIX,
+    registry::UnitRegistry, Num,
+};
+
+/// Coherent SI units, which are tried with every metric prefix that is a power of 1000.
+const SI_UNITS: &[&str] = &[
+    "m", "g", "s", "A", "K", "mol", "cd", "rad", "sr", "Hz", "N", "Pa", "J", "W", "C", "V", "F",
+    "Ω", "S", "Wb", "T", "H", "lm", "lx", "Bq", "Gy", "Sv", "kat",
+];
+
+/// Common units outside of SI, which are also tried with [`UnitSystem::Any`].
+const COMMON_UNITS: &[&str] = &[
+    "min",
+    "h",
+    "d",
+    "wk",
+    "julian year",
+    "deg",
+    "arcmin",
+    "arcsec",
+    "in",
+    "ft",
+    "yd",
+    "mi",
+    "nmi",
+    "au",
+    "ly",
+    "pc",
+    "lb",
+    "t",
+    "degc",
+    "degf",
+    "mph",
+    "knot",
+    "Wh",
+    "eV",
+    "cal",
+    "BTU",
+    "psi",
+    "bar",
+    "atm",
+    "lbf",
+];
+
+/// Which units to consider when picking one automatically.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum UnitSystem {
+    /// Only SI units with metric prefixes.
+    Si,
+    /// SI units and common units from other systems.
+    #[default]
+    Any,
+}
+
+impl UnitSystem {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "si" | "metric" => Self::Si,
+            "any" | "" => Self::Any,
+            _ => return None,
+        })
+    }
+}
+
+/// Picks the unit that shows a quantity most readably.
+/// Prefers values between 1 and 1000, and between those, the largest unit.
+/// Returns `None` if no known unit matches the dimensions of the quantity.
+pub fn best_unit(
+    quantity: &Quantity,
+    system: UnitSystem,
+    registry: &UnitRegistry,
+) -> Option<String> {
+    let mut units = match system {
+        UnitSystem::Si => SI_UNITS.iter().map(|x| (x, true)).collect::<Vec<_>>(),
+        UnitSystem::Any => SI_UNITS
+            .iter()
+            .map(|x| (x, true))
+            .chain(COMMON_UNITS.iter().map(|x| (x, false)))
+            .collect(),
+    };
+    // Quantities without a named unit, like speed or density, still have a usual unit expression.
+    let kinds = quantity.dimensions.kinds();
+    units.extend(
+        (kinds.iter().flat_map(|x| x.units))
+            .filter(|x| !SI_UNITS.contains(x))
+            .map(|x| (x, false)),
+    );
+
+    let mut best: Option<(Num, Num, String)> = None;
+    for (unit, prefixed) in units {
+        let Ok(dimensions) = Dimensions::parse_with(unit, registry) else {
+            continue;
+        };
+        if dimensions != quantity.dimensions {
+            continue;
+        }
+        // Don't move a quantity without an offset, like `300 K`, onto a scale with a different zero, like Celsius.
+        if dimensions.has_offset() && !quantity.dimensions.has_offset() {
+            continue;
+        }
+
+        let Ok(value) = quantity
+            .dimensions
+            .convert(&dimensions, quantity.value, false)
+        else {
+            continue;
+        };
+
+        let prefixes = [("", 0)].into_iter().chain(
+            METRIC_PREFIX
+                .iter()
+                .filter(|x| prefixed && x.power % 3 == 0)
+                .map(|x| (x.symbol, x.power)),
+        );
+
+        for (prefix, power) in prefixes {
+            let value = value / (10 as Num).powi(power);
+            let score = readability(value);
+            let better = match &best {
+                Some((best_score, best_value, _)) => {
+                    score < *best_score || (score == *best_score && value.abs() < best_value.abs())
+                }
+                None => true,
+            };
+
+            if better {
+                best = Some((score, value, format!("{prefix}{unit}")));
+            }
+        }
+    }
+
+    best.map(|(_, _, unit)| unit)
+}
+
+/// How many orders of magnitude a value is outside of [1, 1000).
+fn readability(value: Num) -> Num {
+    let magnitude = value.abs().log10();
+    if !magnitude.is_finite() {
+        return 0.0;
+    }
+
+    if magnitude < 0.0 {
+        -magnitude
+    } else {
+        (magnitude - 3.0).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{best_unit, UnitSystem};
+    use crate::{input::expression::ExpressionParser, registry::UnitRegistry};
+
+    fn best(inp: &str, system: UnitSystem) -> String {
+        let quantity = ExpressionParser::parse(inp).unwrap().evaluate().unwrap();
+        best_unit(&quantity, system, UnitRegistry::builtin()).unwrap()
+    }
+
+    #[test]
+    fn test_best_unit() {
+        assert_eq!(best("0.00003 GHz", UnitSystem::Si), "kHz");
+        assert_eq!(best("90000 s", UnitSystem::Any), "d");
+        assert_eq!(best("90000 s", UnitSystem::Si), "ks");
+        assert_eq!(best("0.0000025 m", UnitSystem::Si), "μm");
+        assert_eq!(best("2500 g", UnitSystem::Si), "kg");
+        assert_eq!(best("1 kg*m^2/s^2", UnitSystem::Si), "J");
+        assert_eq!(best("0 m", UnitSystem::Si), "m");
+        assert_eq!(best("5 g/cm^3", UnitSystem::Si), "kg/m^3");
+        assert_eq!(best("300 K", UnitSystem::Any), "K");
+        assert_eq!(best("3000 K", UnitSystem::Any), "kK");
+        assert_eq!(best("300 degF", UnitSystem::Any), "degc");
+    }
+}
@@ -0,0 +1,437 @@
+//! A flat listing of every unit, derived unit, shorthand and prefix in a registry.
+//! With the `serde` feature it can be exported as JSON, TOML or CSV.
+
+use anyhow::{Context, Result};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{
+    dimension::{Dimensions, Unit},
+    prefix::METRIC_PREFIX,
+    registry::UnitRegistry,
+    units::{Conversion, ConversionType},
+    Num,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Catalog {
+    pub units: Vec<CatalogUnit>,
+    pub prefixes: Vec<CatalogPrefix>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CatalogUnit {
+    pub name: String,
+    pub kind: UnitKind,
+    /// The unit space of a unit, or the module a derived unit is defined in, like `force`.
+    pub group: String,
+    pub aliases: Vec<String>,
+    /// The unit space, or the spaces a derived unit is made of, like `[mass] [length]² [time]⁻²`.
+    pub space: String,
+    pub metric: bool,
+    /// Only included with the `wacky_units` feature.
+    pub wacky: bool,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    /// The value of one of this unit in the base units of its spaces, ignoring any offset.
+    /// Missing for units that are not linear in the base unit.
+    pub factor: Option<Num>,
+    /// What zero in this unit is in the base units, like 273.15 for degrees Celsius.
+    pub offset: Option<Num>,
+    /// The units a derived unit or shorthand expands to, like `kilogram*meter*second^-2`.
+    pub definition: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "lowercase"))]
+pub enum UnitKind {
+    Unit,
+    Derived,
+    Shorthand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CatalogPrefix {
+    pub name: String,
+    pub symbol: String,
+    /// The power of ten the prefix multiplies by.
+    pub power: i32,
+}
+
+/// Formats the catalog can be exported as.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Toml,
+    /// One row per unit, prefixes are not included.
+    Csv,
+}
+
+impl Catalog {
+    /// Lists every unit and prefix in the registry.
+    /// Fails if a derived unit or shorthand can't be expanded.
+    pub fn new(registry: &UnitRegistry) -> Result<Self> {
+        let units = (registry.units().iter()).map(|&x| ConversionType::Conversion(x));
+        let derived =
+            (registry.derived_units().iter()).map(|&x| ConversionType::DerivedConversion(x));
+        let shorthands = (registry.shorthands().iter()).map(|&x| ConversionType::Shorthand(x));
+
+        Ok(Self {
+            units: units
+                .chain(derived)
+                .chain(shorthands)
+                .map(CatalogUnit::new)
+                .collect::<Result<_>>()?,
+            prefixes: registry
+                .prefixes()
+                .iter()
+                .map(|x| CatalogPrefix {
+                    name: x.name.to_owned(),
+                    symbol: x.symbol.to_owned(),
+                    power: x.power,
+                })
+                .collect(),
+        })
+    }
+
+    /// Splits the units into their groups, keeping the order groups first appear in.
+    pub fn groups(&self) -> Vec<(&str, Vec<&CatalogUnit>)> {
+        let mut groups = Vec::<(&str, Vec<&CatalogUnit>)>::new();
+        for unit in &self.units {
+            match groups.iter_mut().find(|x| x.0 == unit.group) {
+                Some((_, units)) => units.push(unit),
+                None => groups.push((&unit.group, vec![unit])),
+            }
+        }
+        groups
+    }
+
+    /// Finds units whose name, aliases or description contain the query, ignoring case.
+    /// Units with a matching name or alias come before ones that only match in their description.
+    pub fn search(&self, query: &str) -> Vec<&CatalogUnit> {
+        let query = query.to_lowercase();
+        let names = |x: &CatalogUnit| {
+            [&x.name]
+                .into_iter()
+                .chain(&x.aliases)
+                .map(|x| x.to_lowercase())
+                .collect::<Vec<_>>()
+        };
+
+        let mut found = self
+            .units
+            .iter()
+            .filter_map(|unit| {
+                let names = names(unit);
+                let rank = if names.contains(&query) {
+                    0
+                } else if names.iter().any(|x| x.contains(&query)) {
+                    1
+                } else if (unit.description.as_ref())
+                    .is_some_and(|x| x.to_lowercase().contains(&query))
+                {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, unit))
+            })
+            .collect::<Vec<_>>();
+        found.sort_by_key(|x| x.0);
+        found.into_iter().map(|x| x.1).collect()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn export(&self, format: ExportFormat) -> Result<String> {
+        Ok(match format {
+            ExportFormat::Json => serde_json::to_string_pretty(self)?,
+            ExportFormat::Toml => toml::to_string(self)?,
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                for unit in &self.units {
+                    writer.serialize(CsvUnit::from(unit))?;
+                }
+                String::from_utf8(writer.into_inner()?)?
+            }
+        })
+    }
+}
+
+impl CatalogUnit {
+    pub fn new(conversion: ConversionType) -> Result<Self> {
+        let expanded = |name| -> Result<Self> {
+            let dimensions = Dimensions::from_conversion(conversion.clone(), 0.0)
+                .with_context(|| format!("Could not expand `{name}`"))?;
+            Ok(CatalogUnit::expanded(name, &dimensions))
+        };
+
+        Ok(match &conversion {
+            ConversionType::Conversion(x) => Self::unit(x),
+            ConversionType::DerivedConversion(x) => CatalogUnit {
+                kind: UnitKind::Derived,
+                group: x.group().to_owned(),
+                aliases: x.aliases.iter().map(|x| x.to_string()).collect(),
+                metric: x.metric,
+                description: documentation(x.description),
+                link: documentation(x.link),
+                ..expanded(x.name)?
+            },
+            ConversionType::Shorthand(x) => CatalogUnit {
+                kind: UnitKind::Shorthand,
+                group: "shorthand".to_owned(),
+                metric: x.metric,
+                ..expanded(x.name)?
+            },
+        })
+    }
+
+    fn unit(unit: &'static Conversion) -> Self {
+        let linear = unit.transform.linear();
+
+        Self {
+            name: unit.name.to_owned(),
+            kind: UnitKind::Unit,
+            group: unit.space.to_string(),
+            aliases: unit.aliases.iter().map(|x| x.to_string()).collect(),
+            space: unit.space.to_string(),
+            metric: unit.metric,
+            wacky: unit.wacky,
+            #[cfg(feature = "documentation")]
+            description: documentation(unit.description),
+            #[cfg(not(feature = "documentation"))]
+            description: None,
+            #[cfg(feature = "documentation")]
+            link: documentation(unit.link),
+            #[cfg(not(feature = "documentation"))]
+            link: None,
+            factor: linear.map(|x| x.0),
+            offset: linear.map(|x| x.1).filter(|&x| x != 0.0),
+            definition: None,
+        }
+    }
+
+    fn expanded(name: &str, dimensions: &Dimensions) -> Self {
+        // Only gauge pressures have special units with an offset, which is in mPa like the other base units of pressure.
+        let (offsets, units) = (dimensions.units().iter())
+            .partition::<Vec<&Unit>, _>(|x| x.is_special() && x.has_offset());
+        let mut definition = units
+            .iter()
+            .map(|x| {
+                if x.is_special() {
+                    return x.to_base(1.0).to_string();
+                }
+
+                let prefix = METRIC_PREFIX
+                    .iter()
+                    .find(|p| p.power as Num == x.sci_exponent())
+                    .map_or("", |p| p.name);
+                unit_name(&format!("{prefix}{}", x.conversion().name), x.power())
+            })
+            .collect::<Vec<_>>()
+            .join("*");
+        for offset in offsets {
+            definition += &format!(" + {} Pa", offset.to_base(0.0) / 1e3);
+        }
+
+        Self {
+            name: name.to_owned(),
+            kind: UnitKind::Unit,
+            group: String::new(),
+            aliases: Vec::new(),
+            space: format!("{:#}", dimensions.simplify()),
+            metric: false,
+            wacky: false,
+            description: None,
+            link: None,
+            factor: dimensions.linear().map(|x| x.0),
+            offset: dimensions.linear().map(|x| x.1).filter(|&x| x != 0.0),
+            definition: Some(definition),
+        }
+    }
+}
+
+/// A description or link, which some units leave empty.
+fn documentation(text: Option<&str>) -> Option<String> {
+    text.filter(|x| !x.is_empty()).map(str::to_owned)
+}
+
+/// Writes a unit name raised to a power, like `second^-2`.
+pub(crate) fn unit_name(name: &str, power: Num) -> String {
+    match power {
+        1.0 => name.to_owned(),
+        power => format!("{name}^{power}"),
+    }
+}
+
+/// A unit as a CSV row, which can't have lists.
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct CsvUnit<'a> {
+    name: &'a str,
+    kind: UnitKind,
+    group: &'a str,
+    aliases: String,
+    space: &'a str,
+    metric: bool,
+    wacky: bool,
+    description: Option<&'a str>,
+    link: Option<&'a str>,
+    factor: Option<Num>,
+    offset: Option<Num>,
+    definition: Option<&'a str>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> From<&'a CatalogUnit> for CsvUnit<'a> {
+    fn from(unit: &'a CatalogUnit) -> Self {
+        Self {
+            name: &unit.name,
+            kind: unit.kind,
+            group: &unit.group,
+            aliases: unit.aliases.join(" "),
+            space: &unit.space,
+            metric: unit.metric,
+            wacky: unit.wacky,
+            description: unit.description.as_deref(),
+            link: unit.link.as_deref(),
+            factor: unit.factor,
+            offset: unit.offset,
+            definition: unit.definition.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{Catalog, UnitKind};
+    use crate::{registry::UnitRegistry, units::derived::DerivedConversion};
+
+    #[test]
+    fn test_catalog() {
+        let catalog = Catalog::new(UnitRegistry::builtin()).unwrap();
+        let unit = |name: &str| catalog.units.iter().find(|x| x.name == name).unwrap();
+
+        let foot = unit("foot");
+        assert_eq!(foot.kind, UnitKind::Unit);
+        assert_eq!(foot.space, "length");
+        assert_eq!(foot.factor, Some(0.3048));
+        assert!(foot.aliases.contains(&"ft".to_owned()));
+
+        let celsius = unit("celsius");
+        assert_eq!(celsius.factor, Some(1.0));
+        assert_eq!(celsius.offset, Some(273.15));
+        assert_eq!(foot.offset, None);
+
+        let psig = unit("psig");
+        assert_relative_eq!(psig.factor.unwrap(), unit("psi").factor.unwrap());
+        assert_relative_eq!(psig.offset.unwrap(), 101_325_000.0);
+        assert!(psig
+            .definition
+            .as_deref()
+            .unwrap()
+            .ends_with(" + 101325 Pa"));
+
+        let newton = unit("newton");
+        assert_eq!(newton.kind, UnitKind::Derived);
+        assert_eq!(
+            newton.definition.as_deref(),
+            Some("kilogram*meter*second^-2")
+        );
+        assert_eq!(newton.factor, Some(1000.0));
+
+        let mph = unit("mph");
+        assert_eq!(mph.kind, UnitKind::Shorthand);
+        assert_eq!(mph.definition.as_deref(), Some("statutemile*hour^-1"));
+
+        assert_eq!(catalog.prefixes.len(), 24);
+    }
+
+    #[test]
+    fn test_unexpandable_unit() {
+        let mut registry = UnitRegistry::new();
+        registry
+            .add_derived(DerivedConversion {
+                name: "nothing",
+                description: None,
+                link: None,
+                expand: &[],
+                aliases: &[],
+                metric: false,
+                module: "user",
+            })
+            .unwrap();
+
+        assert!(Catalog::new(&registry).is_err());
+    }
+
+    #[test]
+    fn test_groups() {
+        let catalog = Catalog::new(UnitRegistry::builtin()).unwrap();
+        let groups = catalog.groups();
+        let group = |name: &str| &groups.iter().find(|x| x.0 == name).unwrap().1;
+
+        assert!(group("length").iter().any(|x| x.name == "foot"));
+        assert!(group("force").iter().any(|x| x.name == "newton"));
+        assert!(group("shorthand").iter().any(|x| x.name == "mph"));
+        #[cfg(feature = "wacky_units")]
+        assert_eq!(
+            catalog
+                .units
+                .iter()
+                .find(|x| x.name == "smoot")
+                .map(|x| x.wacky),
+            Some(true)
+        );
+        assert_eq!(
+            catalog
+                .units
+                .iter()
+                .find(|x| x.name == "foot")
+                .map(|x| x.wacky),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let catalog = Catalog::new(UnitRegistry::builtin()).unwrap();
+        let names = |query| {
+            (catalog.search(query).iter())
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("FTN"), ["fortnight"]);
+        assert_eq!(names("ft")[0], "foot");
+        #[cfg(feature = "documentation")]
+        assert_eq!(names("million astronomical"), ["siriometer"]);
+        assert!(names("notaunit").is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_export() {
+        use super::ExportFormat;
+
+        let catalog = Catalog::new(UnitRegistry::builtin()).unwrap();
+        let json = catalog.export(ExportFormat::Json).unwrap();
+        assert!(json.contains(r#""name": "foot""#));
+        assert!(catalog
+            .export(ExportFormat::Toml)
+            .unwrap()
+            .contains("[[prefixes]]"));
+
+        let csv = catalog.export(ExportFormat::Csv).unwrap();
+        assert!(csv.starts_with(
+            "name,kind,group,aliases,space,metric,wacky,description,link,factor,offset,definition\n"
+        ));
+        assert_eq!(csv.lines().count(), catalog.units.len() + 1);
+    }
+}
@@ -0,0 +1,86 @@
+//! Finding every unit an expression can be converted to.
+
+use anyhow::Result;
+
+use crate::{
+    catalog::CatalogUnit, dimension::Dimensions, registry::UnitRegistry, units::ConversionType, Num,
+};
+
+#[derive(Debug, Clone)]
+pub struct Compatible {
+    pub unit: CatalogUnit,
+    /// The value of one of the expression in this unit.
+    /// Offsets are ignored, so for units like `degC` or `psig` this is the size of a difference.
+    pub value: Num,
+    /// Whether the unit has an offset that was ignored, like `degF` or `psig`.
+    pub offset: bool,
+}
+
+/// Lists every unit, derived unit and shorthand with the same dimensions as a unit expression, like `kg*m/s^2`.
+pub fn compatible(expression: &str, registry: &UnitRegistry) -> Result<Vec<Compatible>> {
+    let from = Dimensions::parse_with(expression, registry)?;
+    let units = (registry.units().iter())
+        .filter(|x| !x.special)
+        .map(|&x| ConversionType::Conversion(x))
+        .chain((registry.derived_units().iter()).map(|&x| ConversionType::DerivedConversion(x)))
+        .chain((registry.shorthands().iter()).map(|&x| ConversionType::Shorthand(x)));
+
+    let mut out = Vec::new();
+    for unit in units {
+        let to = Dimensions::from_conversion(unit.clone(), 0.0)?;
+        if to != from {
+            continue;
+        }
+
+        // Converting `1 degC` as a value would give 274.15 kelvin but 1 deltacelsius, so compare scales instead.
+        let value = match (from.linear(), to.linear()) {
+            (Some((from, _)), Some((to, _))) => from / to,
+            _ => from.convert(&to, 1.0, false)?,
+        };
+        out.push(Compatible {
+            value,
+            offset: to.has_offset(),
+            unit: CatalogUnit::new(unit)?,
+        });
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::compatible;
+    use crate::registry::UnitRegistry;
+
+    #[test]
+    fn test_compatible() {
+        let registry = UnitRegistry::builtin();
+        let found = compatible("kg*m/s^2", registry).unwrap();
+        let value = |name: &str| found.iter().find(|x| x.unit.name == name).unwrap().value;
+
+        assert_relative_eq!(value("newton"), 1.0);
+        assert_relative_eq!(value("poundforce"), 1.0 / 4.448222);
+        assert!(found
+            .iter()
+            .all(|x| x.unit.space == "[mass] [length] [time]⁻²"));
+
+        let found = compatible("psi", registry).unwrap();
+        assert!(found.iter().any(|x| x.unit.name == "pascal"));
+        assert!(found.iter().all(|x| x.unit.name != "newton"));
+        let psig = found.iter().find(|x| x.unit.name == "psig").unwrap();
+        assert_relative_eq!(psig.value, 1.0);
+        assert!(psig.offset);
+
+        let found = compatible("degC", registry).unwrap();
+        let kelvin = found.iter().find(|x| x.unit.name == "kelvin").unwrap();
+        assert_relative_eq!(kelvin.value, 1.0);
+        assert!(!kelvin.offset);
+        let fahrenheit = found.iter().find(|x| x.unit.name == "fahrenheit").unwrap();
+        assert_relative_eq!(fahrenheit.value, 1.8);
+        assert!(fahrenheit.offset);
+
+        assert!(compatible("notaunit", registry).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Plain text unit definitions, for adding units without recompiling.
+//!
+//! Each line defines one unit as a number and a unit expression, in the same language as the rest of the input:
+//! ```text
+//! # Lines starting with `#` are comments.
+//! smoot = 1.7018 m
+//! tick, tk = 15.625 ms  "One server tick"
+//! Wh = W*h  [metric]
+//! ```
+//! Names before the `=` are separated by commas, the first one is the unit's name and the rest are aliases.
+//! After the unit expression, `[metric]` allows metric prefixes and a quoted string documents the unit.
+//! Definitions can use any unit defined on an earlier line.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    dimension::{Dimensions, Unit},
+    input::number::NumberLexer,
+    registry::UnitRegistry,
+    units::{derived::DerivedConversion, Conversion, Space, Transform},
+    Num,
+};
+
+/// One line of a definitions file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// The number of `unit` in one of the defined unit.
+    pub value: Num,
+    /// The unit expression the unit is defined in terms of.
+    pub unit: String,
+    pub metric: bool,
+    pub description: Option<String>,
+}
+
+impl Definition {
+    /// Parses a line of a definitions file.
+    /// Returns `None` for blank lines and comments.
+    pub fn parse(line: &str) -> Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        let Some((names, mut rest)) = line.split_once('=') else {
+            bail!("Expected `=` after the unit name");
+        };
+
+        let mut names = names.split(',').map(|x| x.trim().to_owned());
+        let name = names.next().unwrap();
+        let aliases = names.collect::<Vec<_>>();
+        if name.is_empty() || aliases.iter().any(|x| x.is_empty()) {
+            bail!("Unit names can not be empty");
+        }
+        if let Some(name) = [&name]
+            .into_iter()
+            .chain(&aliases)
+            .find(|x| x.contains(char::is_whitespace))
+        {
+            bail!("Unit names can not contain spaces, found `{name}`");
+        }
+
+        let mut description = None;
+        if let Some(doc) = rest.trim_end().strip_suffix('"') {
+            let Some(start) = doc.rfind('"') else {
+                bail!("Unterminated description, missing the opening `\"`");
+            };
+            description = Some(doc[start + 1..].to_owned());
+            rest = &doc[..start];
+        }
+
+        let mut metric = false;
+        if let Some(flags) = rest.trim_end().strip_suffix(']') {
+            let Some(start) = flags.rfind('[') else {
+                bail!("Unterminated flags, missing the opening `[`");
+            };
+            for flag in flags[start + 1..]
+                .split([',', ' '])
+                .filter(|x| !x.is_empty())
+            {
+                match flag {
+                    "metric" => metric = true,
+                    _ => bail!("Unknown flag `{flag}`"),
+                }
+            }
+            rest = &flags[..start];
+        }
+
+        let chars = rest.trim().chars().collect::<Vec<_>>();
+        let (value, used) = match chars.first() {
+            Some(x) if x.is_ascii_digit() || matches!(x, '.' | '-' | '+') => {
+                let (literal, used) = NumberLexer::lex(&chars)?;
+                (literal.value, used)
+            }
+            Some(_) => (1.0, 0),
+            None => bail!("Missing the definition of `{name}`"),
+        };
+
+        Ok(Some(Self {
+            name,
+            aliases,
+            value,
+            unit: chars[used..].iter().collect::<String>().trim().to_owned(),
+            metric,
+            description,
+        }))
+    }
+
+    /// Adds the unit to the registry as a derived unit, replacing any unit with the same name or aliases.
+    pub fn define(&self, registry: &mut UnitRegistry) -> Result<&'static DerivedConversion> {
+        let dimensions = Dimensions::parse_native(&self.unit, registry)?;
+        let mut expand = dimensions.units().to_vec();
+        if self.value != 1.0 || expand.is_empty() {
+            let constant = Conversion {
+                special: true,
+                ..Conversion::new("virtual-unit", Space::Dynamic, Transform::scale(self.value))
+            };
+            expand.push(Unit::new(leak(constant), 1.0, 0.0));
+        }
+
+        // Derived units are matched on their lowercase name, so keep the name as written in the aliases too.
+        let mut aliases = self.aliases.iter().map(|x| leak_str(x)).collect::<Vec<_>>();
+        if self.name.to_ascii_lowercase() != self.name {
+            aliases.insert(0, leak_str(&self.name));
+        }
+
+        Ok(registry.replace_derived(DerivedConversion {
+            name: leak_str(&self.name),
+            description: self.description.as_deref().map(leak_str),
+            link: None,
+            expand: Box::leak(expand.into_boxed_slice()),
+            aliases: Box::leak(aliases.into_boxed_slice()),
+            metric: self.metric,
+            module: "user",
+        }))
+    }
+}
+
+impl UnitRegistry {
+    /// Adds every unit of a definitions file.
+    /// Returns the number of units defined.
+    pub fn load_definitions(&mut self, src: &str) -> Result<usize> {
+        let mut count = 0;
+        for (i, line) in src.lines().enumerate() {
+            let defined = Definition::parse(line)
+                .and_then(|def| def.map(|def| def.define(self)).transpose())
+                .with_context(|| {
+                    format!("Invalid definition on line {}: `{}`", i + 1, line.trim())
+                })?;
+            count += defined.is_some() as usize;
+        }
+
+        Ok(count)
+    }
+
+    /// Reads a definitions file and adds all of its units.
+    pub fn load_file(&mut self, path: &Path) -> Result<usize> {
+        let src = fs::read_to_string(path)
+            .with_context(|| format!("Could not read definitions file `{}`", path.display()))?;
+        self.load_definitions(&src)
+            .with_context(|| format!("Error in definitions file `{}`", path.display()))
+    }
+}
+
+/// The per-user definitions file, loaded by the CLI if it exists.
+/// Usually `~/.config/unit_convert/units.txt`.
+pub fn user_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|x| x.join("unit_convert").join("units.txt"))
+}
+
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
+
+fn leak_str(s: &str) -> &'static str {
+    Box::leak(s.to_owned().into_boxed_str())
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::Definition;
+    use crate::{dimension::Dimensions, registry::UnitRegistry};
+
+    #[test]
+    fn test_parse() {
+        let def = Definition::parse(r#"tick, tk = 15.625 ms [metric] "One server tick""#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(def.name, "tick");
+        assert_eq!(def.aliases, ["tk"]);
+        assert_eq!(def.value, 15.625);
+        assert_eq!(def.unit, "ms");
+        assert!(def.metric);
+        assert_eq!(def.description.as_deref(), Some("One server tick"));
+
+        let def = Definition::parse("Wh = W*h").unwrap().unwrap();
+        assert_eq!((def.value, def.unit.as_str()), (1.0, "W*h"));
+
+        assert_eq!(Definition::parse("  # comment").unwrap(), None);
+        assert_eq!(Definition::parse("").unwrap(), None);
+        assert!(Definition::parse("smoot 1.7 m").is_err());
+        assert!(Definition::parse("smoot = 1.7 m [imperial]").is_err());
+        assert!(Definition::parse("smoot =").is_err());
+        assert!(Definition::parse("half smoot = 0.5 smoot").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let mut registry = UnitRegistry::new();
+        let count = registry
+            .load_definitions(
+                "# Custom units\n\
+                 smoot = 1.7018 m\n\
+                 halfsmoot = 0.5 smoot\n\
+                 tick, tk = 15.625 ms [metric]\n",
+            )
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let convert = |from: &str, to: &str| {
+            let from = Dimensions::parse_with(from, &registry).unwrap();
+            let to = Dimensions::parse_with(to, &registry).unwrap();
+            from.convert(&to, 1.0, false).unwrap()
+        };
+        assert_relative_eq!(convert("smoot", "cm"), 170.18);
+        assert_relative_eq!(convert("halfsmoot", "m"), 0.8509);
+        assert_relative_eq!(convert("s", "tk"), 64.0);
+        assert_relative_eq!(convert("ks", "ktick"), 64.0);
+    }
+
+    #[test]
+    fn test_load_error() {
+        let mut registry = UnitRegistry::new();
+        let err = registry
+            .load_definitions("smoot = 1.7018 m\n\nbad = 3 notaunit\n")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid definition on line 3: `bad = 3 notaunit`"
+        );
+    }
+}
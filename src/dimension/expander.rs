@@ -23,9 +23,7 @@ impl Expander {
         match token {
             Token::Tree(op, left, right) => match op {
                 Op::Pow => {
-                    let Token::Num(exp) = *right else {
-                        bail!("Invalid exponent. (Expected number literal)");
-                    };
+                    let exp = Self::exponent(*right)?;
                     self._expand(*left, power * exp)?;
                 }
                 Op::Div => {
@@ -55,10 +53,27 @@ impl Expander {
                     self._expand(i, power)?;
                 }
             }
-            Token::Num(..) | Token::Op(..) => unreachable!(),
+            Token::Num(..) => bail!("Unexpected number. (Numbers are only allowed as exponents)"),
+            Token::Op(..) => unreachable!(),
         }
         Ok(())
     }
+
+    /// Evaluates an exponent, which can be a number literal or an expression of them, like `(1/2)`.
+    fn exponent(token: Token) -> Result<Num> {
+        Ok(match token {
+            Token::Num(num) => num,
+            Token::Tree(op, left, right) => {
+                let (left, right) = (Self::exponent(*left)?, Self::exponent(*right)?);
+                match op {
+                    Op::Mul => left * right,
+                    Op::Div => left / right,
+                    Op::Pow => left.powf(right),
+                }
+            }
+            _ => bail!("Invalid exponent. (Expected number literal)"),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -107,4 +122,31 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_expander_rational_exponent() {
+        let inp = Token::Tree(
+            Op::Pow,
+            Box::new(Token::Unit(Unit {
+                conversion: &METER,
+                power: 1.0,
+                sci_exponent: 0.0,
+            })),
+            Box::new(Token::Tree(
+                Op::Div,
+                Box::new(Token::Num(3.0)),
+                Box::new(Token::Num(2.0)),
+            )),
+        );
+
+        let exp = Expander::expand(inp).unwrap();
+        assert_eq!(
+            exp,
+            vec![Unit {
+                conversion: &METER,
+                power: 1.5,
+                sci_exponent: 0.0,
+            }]
+        );
+    }
 }
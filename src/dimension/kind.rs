@@ -0,0 +1,124 @@
+//! Names for the physical quantities a set of dimensions can measure.
+
+use once_cell::sync::Lazy;
+
+use super::Dimensions;
+use crate::registry::UnitRegistry;
+
+/// A named physical quantity, like energy or pressure.
+#[derive(Debug)]
+pub struct QuantityKind {
+    pub name: &'static str,
+    /// A unit expression with the dimensions of the quantity.
+    pub dimensions: &'static str,
+    /// The named units usually used for the quantity, best first.
+    pub units: &'static [&'static str],
+}
+
+impl QuantityKind {
+    const fn new(
+        name: &'static str,
+        dimensions: &'static str,
+        units: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            dimensions,
+            units,
+        }
+    }
+}
+
+#[rustfmt::skip]
+pub const QUANTITY_KINDS: &[QuantityKind] = &[
+    QuantityKind::new("length",                "m",                 &["m"]),
+    QuantityKind::new("mass",                  "g",                 &["kg"]),
+    QuantityKind::new("time",                  "s",                 &["s"]),
+    QuantityKind::new("electric current",      "A",                 &["A"]),
+    QuantityKind::new("temperature",           "K",                 &["K"]),
+    QuantityKind::new("amount of substance",   "mol",               &["mol"]),
+    QuantityKind::new("luminous intensity",    "cd",                &["cd"]),
+    QuantityKind::new("angle",                 "rad",               &["rad", "sr"]),
+    QuantityKind::new("area",                  "m^2",               &["m^2"]),
+    QuantityKind::new("volume",                "m^3",               &["m^3"]),
+    QuantityKind::new("speed",                 "m/s",               &["m/s"]),
+    QuantityKind::new("acceleration",          "m/s^2",             &["m/s^2"]),
+    QuantityKind::new("density",               "g/m^3",             &["kg/m^3"]),
+    QuantityKind::new("frequency",             "s^-1",              &["Hz"]),
+    QuantityKind::new("radioactivity",         "s^-1",              &["Bq"]),
+    QuantityKind::new("force",                 "g*m/s^2",           &["N"]),
+    QuantityKind::new("energy",                "g*m^2/s^2",         &["J"]),
+    QuantityKind::new("torque",                "g*m^2/s^2",         &["N*m"]),
+    QuantityKind::new("power",                 "g*m^2/s^3",         &["W"]),
+    QuantityKind::new("pressure",              "g/(m*s^2)",         &["Pa"]),
+    QuantityKind::new("electric charge",       "A*s",               &["C"]),
+    QuantityKind::new("voltage",               "g*m^2/(s^3*A)",     &["V"]),
+    QuantityKind::new("capacitance",           "s^4*A^2/(g*m^2)",   &["F"]),
+    QuantityKind::new("resistance",            "g*m^2/(s^3*A^2)",   &["Ω"]),
+    QuantityKind::new("conductance",           "s^3*A^2/(g*m^2)",   &["S"]),
+    QuantityKind::new("magnetic flux",         "g*m^2/(s^2*A)",     &["Wb"]),
+    QuantityKind::new("magnetic flux density", "g/(s^2*A)",         &["T"]),
+    QuantityKind::new("inductance",            "g*m^2/(s^2*A^2)",   &["H"]),
+    QuantityKind::new("luminous flux",         "cd*sr",             &["lm"]),
+    QuantityKind::new("illuminance",           "cd*sr/m^2",         &["lx"]),
+    QuantityKind::new("absorbed dose",         "m^2/s^2",           &["Gy"]),
+    QuantityKind::new("equivalent dose",       "m^2/s^2",           &["Sv"]),
+    QuantityKind::new("catalytic activity",    "mol/s",             &["kat"]),
+];
+
+static PARSED: Lazy<Vec<(&QuantityKind, Dimensions)>> = Lazy::new(|| {
+    QUANTITY_KINDS
+        .iter()
+        .map(|x| {
+            let dimensions = Dimensions::parse_native(x.dimensions, UnitRegistry::builtin())
+                .expect("quantity kinds are valid unit expressions");
+            (x, dimensions)
+        })
+        .collect()
+});
+
+impl Dimensions {
+    /// Names the physical quantities with these dimensions.
+    /// Some dimensions measure more than one quantity, like energy and torque, so all matches are returned.
+    pub fn kinds(&self) -> Vec<&'static QuantityKind> {
+        PARSED
+            .iter()
+            .filter(|(_, dimensions)| dimensions == self)
+            .map(|(kind, _)| *kind)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::QUANTITY_KINDS;
+    use crate::dimension::Dimensions;
+
+    fn kinds(s: &str) -> Vec<&str> {
+        let dimensions = Dimensions::from_str(s).unwrap();
+        dimensions.kinds().iter().map(|x| x.name).collect()
+    }
+
+    #[test]
+    fn test_kinds() {
+        assert_eq!(kinds("kg*m^2/s^2"), ["energy", "torque"]);
+        assert_eq!(kinds("N*m"), ["energy", "torque"]);
+        assert_eq!(kinds("psi"), ["pressure"]);
+        assert_eq!(kinds("Hz"), ["frequency", "radioactivity"]);
+        assert_eq!(kinds("V*s"), ["magnetic flux"]);
+        assert!(kinds("m^5").is_empty());
+    }
+
+    #[test]
+    fn test_kind_units() {
+        for kind in QUANTITY_KINDS {
+            let dimensions = Dimensions::from_str(kind.dimensions).unwrap();
+            for unit in kind.units {
+                let unit = Dimensions::from_str(unit).unwrap();
+                assert_eq!(unit, dimensions, "`{unit}` is not a {}", kind.name);
+            }
+        }
+    }
+}
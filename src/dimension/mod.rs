@@ -1,20 +1,36 @@
-use std::{borrow::Cow, fmt::Display, str::FromStr};
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    ops::{Div, Mul},
+    str::FromStr,
+};
 
 use anyhow::Result;
 use hashbrown::HashMap;
 
 use crate::{
-    dimension::{expander::Expander, tokenizer::Tokenizer, tree::Treeifyer},
+    dimension::{
+        expander::Expander,
+        tokenizer::{add_conversion_tokens, Tokenizer},
+        tree::Treeifyer,
+    },
     misc::{NumToStringWithChars, SUPERSCRIPT_CHARSET},
-    units::Conversion,
+    registry::{UnitRegistry, UnitSyntax},
+    ucum,
+    units::{derived::constant, Conversion, ConversionType, Space, Transform},
     Num,
 };
 
 pub mod expander;
+pub mod kind;
+pub mod plan;
 pub mod tokenizer;
 pub mod tree;
 
-#[derive(Debug)]
+/// Tolerance used when comparing non-integer powers, which may not add up exactly.
+pub const POWER_EPSILON: Num = 1e-9;
+
+#[derive(Debug, Clone, Default)]
 pub struct Dimensions {
     units: Vec<Unit>,
 }
@@ -44,6 +60,13 @@ pub enum Op {
     Pow,
 }
 
+/// Functions that can be applied to a parenthesized group, like `sqrt(Hz)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Sqrt,
+    Cbrt,
+}
+
 macro_rules! debug_println {
     ($is_debug:expr) => {
         debug_println!($is_debug,);
@@ -56,18 +79,28 @@ macro_rules! debug_println {
 }
 
 impl Dimensions {
-    pub fn convert(&self, other: &Dimensions, mut value: Num, debug: bool) -> Result<Num> {
+    /// Converts a value in these dimensions to `other`.
+    /// Offsets, like the 273.15 of celsius, are only applied when both sides are a lone absolute temperature, like `degC => degF`.
+    /// Anywhere else, like `degC/min => K/s` or `J/degC => J/K`, temperatures are differences.
+    pub fn convert(&self, other: &Dimensions, value: Num, debug: bool) -> Result<Num> {
+        let absolute = self.is_absolute() && other.is_absolute();
+        Ok(self.convert_steps(other, value, absolute, debug))
+    }
+
+    /// Converts a value one unit at a time, with or without offsets.
+    fn convert_steps(
+        &self,
+        other: &Dimensions,
+        mut value: Num,
+        absolute: bool,
+        debug: bool,
+    ) -> Num {
         for i in &self.units {
-            assert_eq!(i.power.fract(), 0.0);
             let old = value;
-            for _ in 0..i.power.abs() as usize {
-                value = if i.power.signum() > 0.0 {
-                    (i.conversion.to_base)(value)
-                } else {
-                    (i.conversion.from_base)(value)
-                }
-            }
-            value *= (10 as Num).powf(i.sci_exponent * i.power.signum());
+            value = match absolute {
+                true => i.to_base(value),
+                false => i.to_base_delta(value),
+            };
             debug_println!(
                 debug,
                 "{: <8} =[ {: <6} ]=> {}",
@@ -79,17 +112,13 @@ impl Dimensions {
 
         debug_println!(debug);
 
-        for i in &other.units {
-            assert_eq!(i.power.fract(), 0.0);
+        // Offsets have to be removed before scaling, so undo the units in reverse.
+        for i in other.units.iter().rev() {
             let old = value;
-            for _ in 0..i.power.abs() as usize {
-                value = if i.power.signum() > 0.0 {
-                    (i.conversion.from_base)(value)
-                } else {
-                    (i.conversion.to_base)(value)
-                }
-            }
-            value *= (10 as Num).powf(-i.sci_exponent * i.power.signum());
+            value = match absolute {
+                true => i.from_base(value),
+                false => i.from_base_delta(value),
+            };
             debug_println!(
                 debug,
                 "{: <8.5} =[ {: <6} ]=> {:.5}",
@@ -100,7 +129,72 @@ impl Dimensions {
         }
 
         debug_println!(debug);
-        Ok(value)
+        value
+    }
+
+    /// Checks if offsets apply to the dimensions.
+    /// They can't have difference units, and a unit with an offset has to be on its own, like `degC` or `psig` but not `degC/min` or `ΔdegC`.
+    fn is_absolute(&self) -> bool {
+        if self.units.iter().any(|x| x.conversion.delta) {
+            return false;
+        }
+
+        let mut offsets = self.units.iter().filter(|x| x.has_offset());
+        match (offsets.next(), offsets.next()) {
+            (None, _) => true,
+            // Special offsets, like the one in `psig`, are removed when parsing anything but a lone unit.
+            (Some(unit), None) => {
+                unit.power == 1.0 && (unit.conversion.special || self.units.len() == 1)
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks if converting from these dimensions depends on more than a scale factor, like `degC` or `psig`.
+    pub fn has_offset(&self) -> bool {
+        self.units.iter().any(|x| x.has_offset())
+    }
+
+    /// The scale and offset that convert a value in these dimensions to the base units, if every unit is linear.
+    pub fn linear(&self) -> Option<(Num, Num)> {
+        let scale = self
+            .units
+            .iter()
+            .map(Unit::scale)
+            .product::<Option<Num>>()?;
+        let offset = self.units.iter().fold(0.0, |x, unit| unit.to_base(x));
+        Some((scale, offset))
+    }
+
+    pub fn units(&self) -> &[Unit] {
+        &self.units
+    }
+
+    pub fn from_units(units: Vec<Unit>) -> Self {
+        Dimensions { units }
+    }
+
+    /// The dimensions of a single unit, derived unit or shorthand with a prefix of `10^sci_exponent`.
+    pub fn from_conversion(conversion: ConversionType, sci_exponent: Num) -> Result<Self> {
+        let mut tokens = Vec::new();
+        add_conversion_tokens(&mut tokens, conversion, None, sci_exponent);
+        let tree = Treeifyer::treeify(tokens)?;
+        Ok(Dimensions {
+            units: Expander::expand(tree)?,
+        })
+    }
+
+    /// Dimensionless dimensions that scale values by `factor`, which must be positive.
+    pub fn factor(factor: Num) -> Self {
+        Dimensions {
+            units: vec![constant!(1.0, factor.log10())],
+        }
+    }
+
+    /// Raises every unit to `power`.
+    pub fn powf(mut self, power: Num) -> Self {
+        self.units.iter_mut().for_each(|x| x.power *= power);
+        self
     }
 
     /// Only for viewing the unit-space dimensions, do not use for conversions.
@@ -122,6 +216,27 @@ impl Dimensions {
         new_units.retain(|x| x.power != 0.0);
         Dimensions { units: new_units }
     }
+
+    /// Gets the unit space if these dimensions are made of just one, like `m` or `ft*in/mi`.
+    pub fn space(&self) -> Option<Space> {
+        let powers = self.powers();
+        match powers.into_iter().collect::<Vec<_>>()[..] {
+            [(space, power)] if (power - 1.0).abs() < POWER_EPSILON => Some(space),
+            _ => None,
+        }
+    }
+
+    /// Sums the powers of each unit space, ignoring special units.
+    /// Powers that cancel out (within [`POWER_EPSILON`]) are removed.
+    pub fn powers(&self) -> HashMap<Space, Num> {
+        let mut powers = HashMap::new();
+        for unit in self.units.iter().filter(|x| !x.conversion.special) {
+            *powers.entry(unit.conversion.space).or_insert(0.0) += unit.power;
+        }
+
+        powers.retain(|_, v| v.abs() >= POWER_EPSILON);
+        powers
+    }
 }
 
 impl Unit {
@@ -133,9 +248,93 @@ impl Unit {
         }
     }
 
+    pub fn conversion(&self) -> &'static Conversion {
+        self.conversion
+    }
+
+    pub fn power(&self) -> Num {
+        self.power
+    }
+
+    pub fn sci_exponent(&self) -> Num {
+        self.sci_exponent
+    }
+
     pub fn is_special(&self) -> bool {
         self.conversion.special
     }
+
+    /// Converts a value in this unit (raised to its power) to the base units.
+    pub fn to_base(&self, value: Num) -> Num {
+        self.apply(value, self.power)
+    }
+
+    /// Converts a value in the base units to this unit (raised to its power).
+    pub fn from_base(&self, value: Num) -> Num {
+        self.apply(value, -self.power)
+    }
+
+    /// Converts a difference in this unit (raised to its power) to the base units, ignoring any offset.
+    pub fn to_base_delta(&self, value: Num) -> Num {
+        value
+            * self.delta_scale().powf(self.power)
+            * (10 as Num).powf(self.sci_exponent * self.power)
+    }
+
+    /// Converts a difference in the base units to this unit (raised to its power), ignoring any offset.
+    pub fn from_base_delta(&self, value: Num) -> Num {
+        value
+            * self.delta_scale().powf(-self.power)
+            * (10 as Num).powf(-self.sci_exponent * self.power)
+    }
+
+    /// Checks if zero in this unit is not zero in the base unit, like celsius.
+    pub fn has_offset(&self) -> bool {
+        let transform = self.conversion.transform;
+        transform.to_base(0.0) != 0.0
+    }
+
+    /// The value of one of this unit (raised to its power) in the base units, if it is linear.
+    /// Ignores offsets, so this is the size of a difference in the unit.
+    pub fn scale(&self) -> Option<Num> {
+        let (scale, _) = self.conversion.transform.linear()?;
+        Some(scale.powf(self.power) * (10 as Num).powf(self.sci_exponent * self.power))
+    }
+
+    /// The size of this unit in the base unit, the slope of its conversion.
+    fn delta_scale(&self) -> Num {
+        match self.conversion.transform {
+            Transform::Linear { scale, .. } => scale,
+            Transform::Function { to_base, .. } => to_base(1.0) - to_base(0.0),
+        }
+    }
+
+    /// Offsets are only applied to units with a power of one, or minus one to undo them.
+    fn apply(&self, mut value: Num, power: Num) -> Num {
+        let prefix = (10 as Num).powf(self.sci_exponent * power);
+        let (to_base, from_base) = match self.conversion.transform {
+            Transform::Linear { .. } if power == 1.0 => {
+                return self.conversion.transform.to_base(value * prefix)
+            }
+            Transform::Linear { .. } if power == -1.0 => {
+                return self.conversion.transform.from_base(value) * prefix
+            }
+            Transform::Linear { scale, .. } => return value * prefix * scale.powf(power),
+            Transform::Function { to_base, from_base } => (to_base, from_base),
+        };
+
+        let step = if power > 0.0 { to_base } else { from_base };
+        if power.fract() == 0.0 {
+            for _ in 0..power.abs() as usize {
+                value = step(value);
+            }
+        } else {
+            // Roots can only be taken of a scale factor, so non-integer powers treat the unit as linear.
+            value *= step(1.0).powf(power.abs());
+        }
+
+        value * prefix
+    }
 }
 
 impl Op {
@@ -148,18 +347,89 @@ impl Op {
     }
 }
 
+impl Function {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sqrt" => Self::Sqrt,
+            "cbrt" => Self::Cbrt,
+            _ => return None,
+        })
+    }
+
+    /// The power the enclosed group is raised to.
+    pub fn power(&self) -> Num {
+        match self {
+            Self::Sqrt => 1.0 / 2.0,
+            Self::Cbrt => 1.0 / 3.0,
+        }
+    }
+
+    /// Wraps a token in a group raising it to this function's power.
+    fn apply(&self, token: Token) -> Token {
+        Token::Group(vec![token, Token::Op(Op::Pow), Token::Num(self.power())])
+    }
+}
+
 impl FromStr for Dimensions {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tokens = Tokenizer::tokenize(s)?;
+        Self::parse_with(s, UnitRegistry::builtin())
+    }
+}
+
+impl Dimensions {
+    /// Parses dimensions, looking up units in `registry` instead of only the built in units.
+    pub fn parse_with(s: &str, registry: &UnitRegistry) -> Result<Self> {
+        match registry.syntax() {
+            UnitSyntax::Native => Self::parse_native(s, registry),
+            UnitSyntax::Ucum => ucum::parse(s, registry),
+        }
+    }
+
+    /// Parses dimensions written in this crate's own unit syntax, whatever the syntax of the registry.
+    pub fn parse_native(s: &str, registry: &UnitRegistry) -> Result<Self> {
+        let tokens = Tokenizer::tokenize_with(s, registry)?;
+        if tokens.is_empty() {
+            return Ok(Dimensions::default());
+        }
+
+        let lone = tokens.len() == 1;
         let tree = Treeifyer::treeify(tokens)?;
-        let units = Expander::expand(tree)?;
+        let mut units = Expander::expand(tree)?;
+
+        // A gauge unit used in a compound expression, like `psig/s`, is a pressure difference.
+        if !lone {
+            units.retain(|x| !(x.conversion.special && x.has_offset()));
+        }
 
         Ok(Dimensions { units })
     }
 }
 
+/// Combines the units of both dimensions, the unit of the product of two quantities.
+impl Mul for Dimensions {
+    type Output = Dimensions;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self.units.extend(rhs.units);
+        self
+    }
+}
+
+/// Combines the units of both dimensions with the right side inverted, the unit of the quotient of two quantities.
+impl Div for Dimensions {
+    type Output = Dimensions;
+
+    fn div(mut self, rhs: Self) -> Self::Output {
+        self.units.extend(rhs.units.into_iter().map(|unit| Unit {
+            power: -unit.power,
+            ..unit
+        }));
+        self
+    }
+}
+
 impl Display for Dimensions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name: &dyn Fn(&Unit) -> String = if f.alternate() {
@@ -199,20 +469,15 @@ impl Display for Unit {
 
 impl PartialEq for Dimensions {
     fn eq(&self, other: &Self) -> bool {
-        let mut self_dimensions = HashMap::new();
-        for unit in self.units.iter().filter(|x| !x.conversion.special) {
-            *self_dimensions.entry(unit.conversion.space).or_insert(0.0) += unit.power;
-        }
-
-        let mut other_dimensions = HashMap::new();
-        for unit in other.units.iter().filter(|x| !x.conversion.special) {
-            *other_dimensions.entry(unit.conversion.space).or_insert(0.0) += unit.power;
-        }
-
-        self_dimensions.retain(|_, &mut v| v != 0.0);
-        other_dimensions.retain(|_, &mut v| v != 0.0);
-
-        self_dimensions == other_dimensions
+        let self_dimensions = self.powers();
+        let other_dimensions = other.powers();
+
+        self_dimensions.len() == other_dimensions.len()
+            && self_dimensions.iter().all(|(space, power)| {
+                other_dimensions
+                    .get(space)
+                    .is_some_and(|x| (x - power).abs() < POWER_EPSILON)
+            })
     }
 }
 
@@ -234,4 +499,17 @@ mod test {
             assert_eq!(a, j, "Failed on: `{i}`");
         }
     }
+
+    #[test]
+    fn test_root_functions() {
+        let a = Dimensions::from_str("V/sqrt(Hz)").unwrap();
+        let b = Dimensions::from_str("V/Hz^0.5").unwrap();
+        assert_eq!(a, b);
+
+        let a = Dimensions::from_str("cbrt(m^3)").unwrap();
+        let b = Dimensions::from_str("m").unwrap();
+        assert_eq!(a, b);
+
+        assert!(Dimensions::from_str("root(Hz)").is_err());
+    }
 }
@@ -0,0 +1,154 @@
+//! Conversions between two sets of dimensions that are checked once and applied to many values.
+
+use anyhow::{bail, Result};
+
+use super::Dimensions;
+use crate::Num;
+
+/// A reusable conversion between two sets of dimensions, for converting lots of values between the same units.
+/// Units that are a scale and offset of their base unit, which includes all the builtin ones, become a single multiply and add.
+#[derive(Debug, Clone)]
+pub struct ConversionPlan {
+    kind: PlanKind,
+}
+
+#[derive(Debug, Clone)]
+enum PlanKind {
+    /// `value * factor + offset`.
+    Linear { factor: Num, offset: Num },
+    /// Goes through each unit, for units with a [`Transform::Function`](crate::units::Transform::Function).
+    Steps {
+        from: Dimensions,
+        to: Dimensions,
+        absolute: bool,
+    },
+}
+
+impl ConversionPlan {
+    /// Checks that `from` can be converted to `to` and works out how.
+    pub fn new(from: &Dimensions, to: &Dimensions) -> Result<Self> {
+        if from != to {
+            bail!(
+                "Unit dimensions do not match. ({:#} vs {:#})",
+                from.simplify(),
+                to.simplify()
+            );
+        }
+
+        let absolute = from.is_absolute() && to.is_absolute();
+        let kind = match (from.linear(), to.linear()) {
+            (Some((from_scale, _)), Some((to_scale, _))) => PlanKind::Linear {
+                factor: from_scale / to_scale,
+                offset: match absolute {
+                    true => from.convert_steps(to, 0.0, true, false),
+                    false => 0.0,
+                },
+            },
+            _ => PlanKind::Steps {
+                from: from.clone(),
+                to: to.clone(),
+                absolute,
+            },
+        };
+
+        Ok(Self { kind })
+    }
+
+    /// The factor and offset of the conversion, if it is linear.
+    pub fn linear(&self) -> Option<(Num, Num)> {
+        match self.kind {
+            PlanKind::Linear { factor, offset } => Some((factor, offset)),
+            PlanKind::Steps { .. } => None,
+        }
+    }
+
+    pub fn convert(&self, value: Num) -> Num {
+        match &self.kind {
+            PlanKind::Linear { factor, offset } => value * factor + offset,
+            PlanKind::Steps { from, to, absolute } => {
+                from.convert_steps(to, value, *absolute, false)
+            }
+        }
+    }
+
+    /// Converts every value in place.
+    pub fn convert_slice(&self, values: &mut [Num]) {
+        match self.kind {
+            // Kept to a plain loop without branches so it can be vectorized.
+            PlanKind::Linear { factor, offset } => {
+                for value in values {
+                    *value = *value * factor + offset;
+                }
+            }
+            PlanKind::Steps { .. } => values.iter_mut().for_each(|x| *x = self.convert(*x)),
+        }
+    }
+}
+
+impl Dimensions {
+    /// Makes a [`ConversionPlan`] for converting many values from these dimensions to `other`.
+    pub fn plan(&self, other: &Dimensions) -> Result<ConversionPlan> {
+        ConversionPlan::new(self, other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use approx::assert_relative_eq;
+
+    use super::ConversionPlan;
+    use crate::dimension::Dimensions;
+
+    fn plan(from: &str, to: &str) -> ConversionPlan {
+        let from = Dimensions::from_str(from).unwrap();
+        from.plan(&Dimensions::from_str(to).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_plan() {
+        let (factor, offset) = plan("km/h", "m/s").linear().unwrap();
+        assert_relative_eq!(factor, 1.0 / 3.6);
+        assert_eq!(offset, 0.0);
+
+        let plan = plan("degC", "degF");
+        assert_relative_eq!(plan.convert(100.0), 212.0, epsilon = 1e-9);
+
+        let mut values = [-40.0, 0.0, 37.0];
+        plan.convert_slice(&mut values);
+        assert_relative_eq!(values[0], -40.0, epsilon = 1e-9);
+        assert_relative_eq!(values[1], 32.0, epsilon = 1e-9);
+        assert_relative_eq!(values[2], 98.6, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_plan_matches_convert() {
+        for (from, to) in [("degC/min", "K/s"), ("psig", "kPa"), ("ft^2", "cm^2")] {
+            let (from, to) = (
+                Dimensions::from_str(from).unwrap(),
+                Dimensions::from_str(to).unwrap(),
+            );
+            let plan = from.plan(&to).unwrap();
+            for value in [0.0, 1.0, 12.5] {
+                assert_relative_eq!(
+                    plan.convert(value),
+                    from.convert(&to, value, false).unwrap(),
+                    max_relative = 1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let m = Dimensions::from_str("m").unwrap();
+        assert!(m.plan(&Dimensions::from_str("s").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_plan_is_send_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<ConversionPlan>();
+    }
+}
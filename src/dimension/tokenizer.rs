@@ -1,24 +1,33 @@
 use anyhow::{bail, Result};
 
-use super::{Op, Token, Unit};
+use super::{Function, Op, Token, Unit};
 use crate::{
-    prefix,
+    registry::UnitRegistry,
+    suggest,
     units::{derived::constant, ConversionType},
     Num,
 };
 
-pub struct Tokenizer {
+pub struct Tokenizer<'a> {
+    registry: &'a UnitRegistry,
     chars: Box<[char]>,
     index: usize,
     depth: usize,
+    /// The function applied to the group currently being read, if any.
+    function: Option<Function>,
 
     pub(super) tokens: Vec<Token>,
     buffer: String,
 }
 
-impl Tokenizer {
+impl<'a> Tokenizer<'a> {
     pub fn tokenize(raw: &str) -> Result<Vec<Token>> {
-        let mut ctx = Self::new(raw);
+        Tokenizer::tokenize_with(raw, UnitRegistry::builtin())
+    }
+
+    /// Tokenizes a unit expression, looking up units in `registry`.
+    pub fn tokenize_with(raw: &str, registry: &'a UnitRegistry) -> Result<Vec<Token>> {
+        let mut ctx = Self::new(raw, registry);
 
         while ctx.index < ctx.chars.len() {
             let chr = ctx.chars[ctx.index];
@@ -30,8 +39,12 @@ impl Tokenizer {
                     ')' => {
                         ctx.depth -= 1;
                         if ctx.depth == 0 {
-                            ctx.tokens
-                                .push(Token::Group(Tokenizer::tokenize(&ctx.buffer)?));
+                            let group =
+                                Token::Group(Tokenizer::tokenize_with(&ctx.buffer, registry)?);
+                            ctx.tokens.push(match ctx.function.take() {
+                                Some(function) => function.apply(group),
+                                None => group,
+                            });
                             ctx.buffer.clear();
                         }
                     }
@@ -45,7 +58,10 @@ impl Tokenizer {
                 '/' => ctx.add_token(Op::Div)?,
                 '*' => ctx.add_token(Op::Mul)?,
                 '^' => ctx.add_token(Op::Pow)?,
-                '(' => ctx.depth += 1,
+                '(' => {
+                    ctx.open_group()?;
+                    ctx.depth += 1;
+                }
                 ')' => bail!("Unmatched closing parenthesis"),
                 _ => ctx.buffer.push(chr),
             }
@@ -55,11 +71,13 @@ impl Tokenizer {
         Ok(ctx.tokens)
     }
 
-    fn new(input: &str) -> Self {
+    fn new(input: &str, registry: &'a UnitRegistry) -> Self {
         Self {
+            registry,
             chars: input.chars().collect(),
             index: 0,
             depth: 0,
+            function: None,
 
             tokens: Vec::new(),
             buffer: String::new(),
@@ -72,6 +90,18 @@ impl Tokenizer {
         Ok(())
     }
 
+    /// Called before reading a parenthesized group.
+    /// An identifier directly before the group is treated as a function call.
+    fn open_group(&mut self) -> Result<()> {
+        if let Some(function) = Function::from_name(&self.buffer) {
+            self.function = Some(function);
+            self.buffer.clear();
+            return Ok(());
+        }
+
+        self.flush_buffer()
+    }
+
     fn flush_buffer(&mut self) -> Result<()> {
         if self.buffer.is_empty() {
             return Ok(());
@@ -79,8 +109,8 @@ impl Tokenizer {
 
         if let Ok(num) = self.buffer.parse::<Num>() {
             self.tokens.push(Token::Num(num));
-        } else if let Some((conversion, prefix)) =
-            prefix::get(&self.buffer.replace(['_', '-'], " "))
+        } else if let Some((conversion, prefix)) = (self.registry.get(&self.buffer))
+            .or_else(|| self.registry.get(&self.buffer.replace(['_', '-'], " ")))
         {
             add_conversion_tokens(
                 &mut self.tokens,
@@ -89,7 +119,11 @@ impl Tokenizer {
                 prefix.map(|x| x.power as Num).unwrap_or(0.0),
             );
         } else {
-            bail!("Invalid token: {}", self.buffer);
+            bail!(
+                "Invalid token: {}{}",
+                self.buffer,
+                suggest::hint(&self.buffer, self.registry)
+            );
         }
 
         self.buffer.clear();
@@ -205,4 +239,29 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_tokenize_function() {
+        let tokens = Tokenizer::tokenize("m/sqrt(s)").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Unit(Unit {
+                    conversion: &METER,
+                    power: 1.0,
+                    sci_exponent: 0.0,
+                }),
+                Token::Op(Op::Div),
+                Token::Group(vec![
+                    Token::Group(vec![Token::Unit(Unit {
+                        conversion: &SECOND,
+                        power: 1.0,
+                        sci_exponent: 0.0,
+                    })]),
+                    Token::Op(Op::Pow),
+                    Token::Num(0.5),
+                ]),
+            ]
+        );
+    }
 }
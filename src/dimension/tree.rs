@@ -10,12 +10,16 @@ pub struct Treeifyer {
 
 impl Treeifyer {
     pub fn treeify(mut tokens: Vec<Token>) -> Result<Token> {
+        if tokens.is_empty() {
+            bail!("Expected a unit");
+        }
+
         if tokens.len() == 1 {
             let token = tokens.pop().unwrap();
             match token {
                 Token::Group(tokens) => return Treeifyer::treeify(tokens),
-                Token::Unit { .. } => return Ok(token),
-                Token::Op(..) | Token::Tree(..) | Token::Num(..) => {
+                Token::Unit { .. } | Token::Num(..) => return Ok(token),
+                Token::Op(..) | Token::Tree(..) => {
                     unreachable!("Invalid token")
                 }
             }
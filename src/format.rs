@@ -0,0 +1,189 @@
+use clap::ValueEnum;
+use thousands::Separable;
+
+use crate::Num;
+
+/// How numbers are written when displaying conversion results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Notation {
+    /// Full decimal expansion with digit groups, like `9 460 730 472 580 800`.
+    #[default]
+    Plain,
+    /// One digit before the decimal point, like `9.4607304725808e15`.
+    Scientific,
+    /// Exponents restricted to multiples of three, like `9.4607304725808e15` or `94.6e-9`.
+    Engineering,
+}
+
+/// How many digits of a number are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Precision {
+    /// As many digits as needed to represent the number exactly.
+    #[default]
+    Full,
+    /// A fixed number of digits after the decimal point.
+    /// With scientific and engineering notation, this counts the digits of the mantissa.
+    Decimals(usize),
+    /// Rounds to a number of significant figures.
+    SignificantFigures(usize),
+    /// Uses as many significant figures as the input value had.
+    /// Must be resolved with [`Precision::resolve`], otherwise it acts like [`Precision::Full`].
+    MatchInput,
+}
+
+impl Notation {
+    pub fn format(&self, num: Num) -> String {
+        if !num.is_finite() {
+            return num.to_string();
+        }
+
+        match self {
+            Notation::Plain => num.separate_with_spaces(),
+            Notation::Scientific => format!("{num:e}"),
+            Notation::Engineering => to_engineering(&format!("{num:e}")),
+        }
+    }
+
+    pub fn format_with(&self, num: Num, precision: Precision) -> String {
+        if !num.is_finite() {
+            return num.to_string();
+        }
+
+        let figures = match precision {
+            Precision::Full | Precision::MatchInput => return self.format(num),
+            Precision::SignificantFigures(figures) => figures.max(1),
+            Precision::Decimals(decimals) => match self {
+                Notation::Plain => return format!("{num:.decimals$}").separate_with_spaces(),
+                Notation::Scientific => decimals + 1,
+                Notation::Engineering => {
+                    let shift = exponent(&format!("{num:e}")).rem_euclid(3) as usize;
+                    decimals + shift + 1
+                }
+            },
+        };
+
+        let rounded = format!("{:.*e}", figures - 1, num);
+        match self {
+            Notation::Plain => {
+                let decimals = (figures as i32 - 1 - exponent(&rounded)).max(0) as usize;
+                let rounded = rounded.parse::<Num>().unwrap();
+                format!("{rounded:.decimals$}").separate_with_spaces()
+            }
+            Notation::Scientific => rounded,
+            Notation::Engineering => to_engineering(&rounded),
+        }
+    }
+}
+
+impl Precision {
+    /// Replaces [`Precision::MatchInput`] with the significant figures of the input.
+    pub fn resolve(self, input_figures: usize) -> Self {
+        match self {
+            Precision::MatchInput => Precision::SignificantFigures(input_figures),
+            _ => self,
+        }
+    }
+}
+
+/// Counts the significant figures in a number literal, like `0.0050` (2) or `1200` (2).
+/// Trailing zeros are only counted if the literal has a decimal point.
+pub fn significant_figures(literal: &str) -> usize {
+    let mantissa = literal.split(['e', 'E']).next().unwrap_or_default();
+    let has_point = mantissa.contains('.');
+    let digits = mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+
+    let digits = digits.trim_start_matches('0');
+    let digits = if has_point {
+        digits
+    } else {
+        digits.trim_end_matches('0')
+    };
+
+    digits.len().max(1)
+}
+
+fn exponent(scientific: &str) -> i32 {
+    let (_, exponent) = scientific
+        .split_once('e')
+        .expect("Number not in scientific notation");
+    exponent.parse().unwrap()
+}
+
+/// Shifts the decimal point of a number in scientific notation (as formatted by `{:e}`) so the exponent is a multiple of three.
+/// This is done on the digits directly to avoid rounding errors from rescaling the mantissa.
+fn to_engineering(scientific: &str) -> String {
+    let (mantissa, _) = scientific.split_once('e').unwrap();
+    let exponent = exponent(scientific);
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => ("-", mantissa),
+        None => ("", mantissa),
+    };
+
+    let shift = exponent.rem_euclid(3) as usize;
+    let mut digits = mantissa.replace('.', "");
+    while digits.len() <= shift {
+        digits.push('0');
+    }
+
+    let (int, frac) = digits.split_at(shift + 1);
+    let point = if frac.is_empty() { "" } else { "." };
+    format!("{sign}{int}{point}{frac}e{}", exponent - shift as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{significant_figures, Notation, Precision};
+
+    #[test]
+    fn test_scientific() {
+        assert_eq!(Notation::Scientific.format(9.4607e30), "9.4607e30");
+        assert_eq!(Notation::Scientific.format(-0.00025), "-2.5e-4");
+        assert_eq!(Notation::Scientific.format(f64::INFINITY), "inf");
+    }
+
+    #[test]
+    fn test_engineering() {
+        assert_eq!(Notation::Engineering.format(9.4607e30), "9.4607e30");
+        assert_eq!(Notation::Engineering.format(15000.0), "15e3");
+        assert_eq!(Notation::Engineering.format(123456.0), "123.456e3");
+        assert_eq!(Notation::Engineering.format(-0.00025), "-250e-6");
+        assert_eq!(Notation::Engineering.format(0.00001), "10e-6");
+        assert_eq!(Notation::Engineering.format(0.0), "0e0");
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(Notation::Plain.format(1234567.5), "1 234 567.5");
+    }
+
+    #[test]
+    fn test_precision() {
+        let sig = Precision::SignificantFigures(3);
+        assert_eq!(Notation::Plain.format_with(22.369362920544024, sig), "22.4");
+        assert_eq!(Notation::Plain.format_with(123456.0, sig), "123 000");
+        assert_eq!(Notation::Scientific.format_with(123456.0, sig), "1.23e5");
+        assert_eq!(Notation::Engineering.format_with(15000.0, sig), "15.0e3");
+
+        let dec = Precision::Decimals(2);
+        assert_eq!(Notation::Plain.format_with(1234.5678, dec), "1 234.57");
+        assert_eq!(Notation::Scientific.format_with(1234.5678, dec), "1.23e3");
+        assert_eq!(Notation::Engineering.format_with(12345.678, dec), "12.35e3");
+    }
+
+    #[test]
+    fn test_significant_figures() {
+        assert_eq!(significant_figures("10"), 1);
+        assert_eq!(significant_figures("1200"), 2);
+        assert_eq!(significant_figures("1200."), 4);
+        assert_eq!(significant_figures("0.0050"), 2);
+        assert_eq!(significant_figures("-3.14e10"), 3);
+        assert_eq!(significant_figures("0"), 1);
+        assert_eq!(
+            Precision::MatchInput.resolve(2),
+            Precision::SignificantFigures(2)
+        );
+    }
+}
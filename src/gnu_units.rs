@@ -0,0 +1,746 @@
+//! Importer for GNU `units` definition files, like `definitions.units`.
+//!
+//! Supports primitive units (`meter !`), prefixes (`kilo- 1000`), units defined by an expression (`inch 2.54 cm`)
+//! and the `!locale`, `!utf8`, `!set`, `!var` and `!include` directives.
+//! Nonlinear units, piecewise linear tables, new primitive units and prefixes that are not a positive power of ten can not be represented,
+//! so they are listed in the [`ImportReport`] instead.
+//!
+//! Units that already exist in the registry keep their built in definition.
+//! Imported units only take metric prefixes if they are exactly a product of metric units, like `rayl kg / m^2 s`.
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{
+    defs::Definition,
+    prefix::Prefix,
+    registry::UnitRegistry,
+    units::{derived::DerivedConversion, ConversionType},
+    Num,
+};
+
+/// The locale used for `!locale` blocks.
+const LOCALE: &str = "en_US";
+
+/// What an import added to the registry, and what it could not.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Names of the units that were added.
+    pub units: Vec<String>,
+    /// Names of the prefixes that were added.
+    pub prefixes: Vec<String>,
+    /// Units and prefixes that were already defined, and kept their existing definition.
+    pub existing: Vec<String>,
+    /// Definitions and directives that could not be imported.
+    pub skipped: Vec<Skipped>,
+}
+
+/// A definition or directive that was not imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    /// The line the definition starts on.
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl UnitRegistry {
+    /// Imports the units of a GNU `units` definitions file.
+    /// `!include` directives are skipped, as there is no file to resolve them against.
+    pub fn import_gnu_units(&mut self, src: &str) -> ImportReport {
+        let mut importer = Importer::new(self);
+        importer
+            .read(src, None)
+            .expect("Only included files can fail to be read");
+        importer.finish()
+    }
+
+    /// Reads and imports a GNU `units` definitions file, following its `!include` directives.
+    pub fn import_gnu_units_file(&mut self, path: &Path) -> Result<ImportReport> {
+        let mut importer = Importer::new(self);
+        importer.read_file(path)?;
+        Ok(importer.finish())
+    }
+}
+
+struct Importer<'a> {
+    registry: &'a mut UnitRegistry,
+    report: ImportReport,
+    /// Variables set by `!set`, which can be overridden by environment variables.
+    vars: HashMap<String, String>,
+    /// Prefix values by name and symbol, including the ones already in the registry.
+    prefixes: HashMap<String, Num>,
+    pending_prefixes: Vec<Entry>,
+    pending_units: Vec<Entry>,
+}
+
+/// A definition waiting to be resolved.
+struct Entry {
+    line: usize,
+    name: String,
+    definition: String,
+}
+
+/// A parsed definition: a number times some units raised to a power.
+#[derive(Debug, PartialEq)]
+struct Expression {
+    factor: Num,
+    units: Vec<(String, Num)>,
+}
+
+impl<'a> Importer<'a> {
+    fn new(registry: &'a mut UnitRegistry) -> Self {
+        let prefixes = registry
+            .prefixes()
+            .iter()
+            .flat_map(|x| {
+                let value = (10 as Num).powi(x.power);
+                [(x.name.to_owned(), value), (x.symbol.to_owned(), value)]
+            })
+            .collect();
+
+        Self {
+            registry,
+            report: ImportReport::default(),
+            vars: HashMap::new(),
+            prefixes,
+            pending_prefixes: Vec::new(),
+            pending_units: Vec::new(),
+        }
+    }
+
+    fn read_file(&mut self, path: &Path) -> Result<()> {
+        let src = fs::read_to_string(path)
+            .with_context(|| format!("Could not read units file `{}`", path.display()))?;
+        self.read(&src, Some(path))
+    }
+
+    /// Reads the definitions of a file.
+    /// Units are only defined in [`Importer::finish`], as definitions can refer to units defined later in the file.
+    fn read(&mut self, src: &str, path: Option<&Path>) -> Result<()> {
+        // Whether each nested `!locale`, `!utf8` or `!var` block is active.
+        let mut blocks = Vec::<bool>::new();
+
+        for (line, text) in logical_lines(src) {
+            let active = blocks.iter().all(|x| *x);
+            if let Some(directive) = text.strip_prefix('!') {
+                let mut args = directive.split_whitespace();
+                let name = args.next().unwrap_or_default();
+                let args = args.collect::<Vec<_>>();
+                match name {
+                    "locale" => blocks.push(args.first() == Some(&LOCALE)),
+                    "utf8" => blocks.push(true),
+                    "var" | "varnot" => {
+                        let value = args.first().and_then(|x| self.var(x));
+                        let matches = value.is_some_and(|x| args[1..].contains(&x.as_str()));
+                        blocks.push(matches == (name == "var"));
+                    }
+                    "endlocale" | "endutf8" | "endvar" => {
+                        blocks.pop();
+                    }
+                    _ if !active => {}
+                    "set" if args.len() == 2 => {
+                        self.vars
+                            .entry(args[0].to_owned())
+                            .or_insert_with(|| args[1].to_owned());
+                    }
+                    "message" | "prompt" => {}
+                    "include" => match (path, args.as_slice()) {
+                        (Some(path), [file]) => self.read_file(&path.with_file_name(file))?,
+                        _ => self.skip(line, &text, "includes are only followed in files"),
+                    },
+                    _ => self.skip(line, &text, "unsupported directive"),
+                }
+                continue;
+            }
+
+            if !active {
+                continue;
+            }
+
+            let (name, definition) = match text.split_once(char::is_whitespace) {
+                Some((name, definition)) => (name, definition.trim()),
+                None => (text.as_str(), ""),
+            };
+            let name = name.strip_prefix('+').unwrap_or(name);
+            let entry = Entry {
+                line,
+                name: name.to_owned(),
+                definition: definition.to_owned(),
+            };
+
+            if name.contains('(') {
+                self.skip(line, &text, "nonlinear units are not supported");
+            } else if name.contains('[') {
+                self.skip(line, &text, "piecewise linear units are not supported");
+            } else if definition.is_empty() {
+                self.skip(line, &text, "missing definition");
+            } else if definition.starts_with('!') {
+                self.primitive(entry);
+            } else if name.ends_with('-') {
+                self.pending_prefixes.push(entry);
+            } else {
+                self.pending_units.push(entry);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Primitive units are the base of a dimension.
+    /// The dimensions are fixed, so these can only be matched with a unit that already exists.
+    fn primitive(&mut self, entry: Entry) {
+        if self.registry.get(&unit_name(&entry.name)).is_some() {
+            self.report.existing.push(entry.name);
+        } else {
+            let text = format!("{} {}", entry.name, entry.definition);
+            self.skip(entry.line, &text, "new primitive units are not supported");
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<String> {
+        env::var(name).ok().or_else(|| self.vars.get(name).cloned())
+    }
+
+    fn skip(&mut self, line: usize, text: &str, reason: &str) {
+        self.report.skipped.push(Skipped {
+            line,
+            text: text.to_owned(),
+            reason: reason.to_owned(),
+        });
+    }
+
+    /// Defines all the read prefixes and units.
+    /// Definitions are retried until no more can be resolved, so the order they are defined in doesn't matter.
+    fn finish(mut self) -> ImportReport {
+        let prefixes = std::mem::take(&mut self.pending_prefixes);
+        for (entry, err) in self.resolve(prefixes, Self::define_prefix) {
+            self.skip_failed(entry, err);
+        }
+
+        let units = std::mem::take(&mut self.pending_units);
+        for (entry, err) in self.resolve(units, Self::define_unit) {
+            self.skip_failed(entry, err);
+        }
+
+        self.report.skipped.sort_by_key(|x| x.line);
+        self.report
+    }
+
+    /// Repeatedly tries to define every entry, returning the ones that never succeeded with their last error.
+    fn resolve(
+        &mut self,
+        mut pending: Vec<Entry>,
+        define: fn(&mut Self, &Entry) -> Result<Resolved>,
+    ) -> Vec<(Entry, anyhow::Error)> {
+        loop {
+            let mut failed = Vec::new();
+            let mut retry = false;
+            for entry in pending {
+                match define(self, &entry) {
+                    Ok(Resolved::Done) => retry = true,
+                    Ok(Resolved::Retry(err)) | Err(err) => failed.push((entry, err)),
+                }
+            }
+
+            if !retry || failed.is_empty() {
+                return failed;
+            }
+            pending = failed.into_iter().map(|x| x.0).collect();
+        }
+    }
+
+    fn skip_failed(&mut self, entry: Entry, err: anyhow::Error) {
+        let reason = err.to_string();
+        let text = format!("{} {}", entry.name, entry.definition);
+        self.skip(entry.line, &text, &reason);
+    }
+
+    fn define_prefix(&mut self, entry: &Entry) -> Result<Resolved> {
+        let name = entry.name.trim_end_matches('-');
+        if self.prefixes.contains_key(name) {
+            self.report.existing.push(entry.name.to_owned());
+            return Ok(Resolved::Done);
+        }
+
+        let expression = parse(&entry.definition, &|_| false)?;
+        let mut value = expression.factor;
+        for (name, power) in expression.units {
+            match self.prefixes.get(name.trim_end_matches('-')) {
+                Some(prefix) => value *= prefix.powf(power),
+                None => return Ok(Resolved::Retry(anyhow!("unknown prefix `{name}`"))),
+            }
+        }
+
+        if value <= 0.0 || !value.is_finite() {
+            bail!("prefixes must be positive");
+        }
+        let power = value.log10().round();
+        if ((10 as Num).powf(power) - value).abs() > value * 1e-9 {
+            bail!("only prefixes that are a power of ten are supported");
+        }
+
+        self.prefixes.insert(name.to_owned(), value);
+        self.report.prefixes.push(name.to_owned());
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        self.registry
+            .add_prefix(Prefix::new(name, name, power as i32))?;
+        Ok(Resolved::Done)
+    }
+
+    fn define_unit(&mut self, entry: &Entry) -> Result<Resolved> {
+        let name = unit_name(&entry.name);
+        if self.registry.find_unit(&name).is_some() {
+            self.report.existing.push(entry.name.to_owned());
+            return Ok(Resolved::Done);
+        }
+
+        let registry = &*self.registry;
+        let expression = parse(&entry.definition, &|x| {
+            registry.get(&unit_name(x)).is_some()
+        })?;
+        let metric = expression.factor == 1.0
+            && (expression.units.iter()).all(|x| {
+                registry
+                    .get(&unit_name(&x.0))
+                    .is_some_and(|x| x.0.is_metric())
+            });
+        let unit = expression
+            .units
+            .iter()
+            .map(|(name, power)| match power {
+                x if *x == 1.0 => name.to_owned(),
+                x => format!("{name}^{x}"),
+            })
+            .collect::<Vec<_>>()
+            .join("*");
+
+        let definition = Definition {
+            name,
+            aliases: Vec::new(),
+            value: expression.factor,
+            unit,
+            metric,
+            description: None,
+        };
+        let unit = match definition.define(self.registry) {
+            Ok(unit) => unit,
+            Err(err) => return Ok(Resolved::Retry(err)),
+        };
+
+        // Some names are valid in GNU units but can't be written in this crate's unit expressions.
+        if !self.resolves_to(&entry.name, unit) {
+            self.registry.remove(&definition.name);
+            bail!("the name can not be used in unit expressions");
+        }
+
+        self.report.units.push(entry.name.to_owned());
+        Ok(Resolved::Done)
+    }
+
+    fn resolves_to(&self, name: &str, unit: &DerivedConversion) -> bool {
+        matches!(
+            self.registry.get(&unit_name(name)),
+            Some((ConversionType::DerivedConversion(x), None)) if std::ptr::eq(x, unit)
+        ) && !name.chars().any(|x| "*/^()".contains(x))
+    }
+}
+
+enum Resolved {
+    Done,
+    /// The definition uses something that may be defined later.
+    Retry(anyhow::Error),
+}
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: `{}` ({})", self.line, self.text, self.reason)
+    }
+}
+
+/// The name a GNU unit is stored under.
+/// The tokenizer reads `_` and `-` in names as spaces.
+fn unit_name(name: &str) -> String {
+    name.replace(['_', '-'], " ")
+}
+
+/// Splits a file into lines with their line number, joining lines ending in `\` and removing comments.
+fn logical_lines(src: &str) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (i, line) in src.lines().enumerate() {
+        let line = line.split('#').next().unwrap();
+        let (line, continues) = match line.trim_end().strip_suffix('\\') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+
+        let (start, mut text) = current.take().unwrap_or((i + 1, String::new()));
+        text.push_str(line);
+        text.push(' ');
+
+        if continues {
+            current = Some((start, text));
+        } else if !text.trim().is_empty() {
+            out.push((start, text.trim().to_owned()));
+        }
+    }
+
+    if let Some((start, text)) = current.filter(|x| !x.1.trim().is_empty()) {
+        out.push((start, text.trim().to_owned()));
+    }
+
+    out
+}
+
+/// Parses a GNU units expression.
+/// Multiplication by juxtaposition (`kg m`) binds tighter than `*`, `/` and `per`,
+/// `|` divides numbers (`1|3`) and a digit at the end of an unknown name is a power (`cm3`).
+/// `is_unit` checks if a name is a unit, so names ending in digits are not split.
+fn parse(s: &str, is_unit: &dyn Fn(&str) -> bool) -> Result<Expression> {
+    let mut parser = Parser {
+        chars: s.chars().collect(),
+        index: 0,
+        is_unit,
+    };
+
+    let expression = parser.expression()?;
+    parser.skip_whitespace();
+    if let Some(chr) = parser.peek() {
+        bail!("unexpected `{chr}` in the definition");
+    }
+
+    Ok(expression)
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    index: usize,
+    is_unit: &'a dyn Fn(&str) -> bool,
+}
+
+impl Parser<'_> {
+    fn expression(&mut self) -> Result<Expression> {
+        self.skip_whitespace();
+        let mut out = if self.peek() == Some('/') {
+            Expression::one()
+        } else {
+            self.product()?
+        };
+
+        loop {
+            self.skip_whitespace();
+            let divide = match self.peek() {
+                Some('*') => false,
+                Some('/') => true,
+                Some('p') if self.at_keyword("per") => {
+                    self.index += 2;
+                    true
+                }
+                Some('+' | '-') => bail!("sums of units are not supported"),
+                _ => return Ok(out),
+            };
+
+            self.index += 1;
+            let rhs = self.product()?;
+            out = out.mul(if divide { rhs.pow(-1.0) } else { rhs });
+        }
+    }
+
+    /// Values next to each other, which are multiplied.
+    fn product(&mut self) -> Result<Expression> {
+        let mut out = self.power()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(x) if is_operator(x) && x != '(' => return Ok(out),
+                Some('p') if self.at_keyword("per") => return Ok(out),
+                None => return Ok(out),
+                _ => out = out.mul(self.power()?),
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression> {
+        let base = self.primary()?;
+        self.skip_whitespace();
+
+        let pow = match (self.peek(), self.chars.get(self.index + 1)) {
+            (Some('^'), _) => 1,
+            (Some('*'), Some('*')) => 2,
+            _ => return Ok(base),
+        };
+        self.index += pow;
+        self.skip_whitespace();
+
+        let exponent = if self.peek() == Some('(') {
+            self.primary()?
+        } else {
+            self.number()?
+        };
+        if !exponent.units.is_empty() {
+            bail!("exponents must be numbers");
+        }
+
+        Ok(base.pow(exponent.factor))
+    }
+
+    fn primary(&mut self) -> Result<Expression> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.index += 1;
+                let inner = self.expression()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    bail!("missing closing parenthesis");
+                }
+                self.index += 1;
+                Ok(inner)
+            }
+            Some(x) if x.is_ascii_digit() || matches!(x, '.' | '-' | '+') => self.number(),
+            Some(x) if !is_operator(x) => self.name(),
+            Some(x) => bail!("unexpected `{x}` in the definition"),
+            None => bail!("unexpected end of the definition"),
+        }
+    }
+
+    /// A number, or a fraction of two numbers like `1|3`.
+    fn number(&mut self) -> Result<Expression> {
+        let mut value = self.decimal()?;
+        if self.peek() == Some('|') {
+            self.index += 1;
+            value /= self.decimal()?;
+        }
+
+        Ok(Expression {
+            factor: value,
+            units: Vec::new(),
+        })
+    }
+
+    fn decimal(&mut self) -> Result<Num> {
+        let start = self.index;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.index += 1;
+        }
+        while matches!(self.peek(), Some(x) if x.is_ascii_digit() || x == '.') {
+            self.index += 1;
+        }
+        if matches!(self.peek(), Some('e' | 'E'))
+            && matches!(self.chars.get(self.index + 1), Some(x) if x.is_ascii_digit() || *x == '-' || *x == '+')
+        {
+            self.index += 2;
+            while matches!(self.peek(), Some(x) if x.is_ascii_digit()) {
+                self.index += 1;
+            }
+        }
+
+        let number = self.chars[start..self.index].iter().collect::<String>();
+        number
+            .parse()
+            .with_context(|| format!("invalid number `{number}`"))
+    }
+
+    fn name(&mut self) -> Result<Expression> {
+        let start = self.index;
+        while matches!(self.peek(), Some(x) if !x.is_whitespace() && !is_operator(x)) {
+            self.index += 1;
+        }
+        let name = self.chars[start..self.index].iter().collect::<String>();
+
+        match name.as_str() {
+            "sqrt" | "cuberoot" if self.peek() == Some('(') => {
+                let power = if name == "sqrt" { 0.5 } else { 1.0 / 3.0 };
+                return Ok(self.primary()?.pow(power));
+            }
+            _ if self.peek() == Some('(') => bail!("functions are not supported"),
+            _ => {}
+        }
+
+        // `cm3` is `cm^3`, unless it's the name of a unit.
+        let mut chars = name.chars().rev();
+        if let (Some(digit @ '2'..='9'), Some(before)) = (chars.next(), chars.next()) {
+            if !(self.is_unit)(&name) && !before.is_ascii_digit() && before != '_' {
+                let stem = name[..name.len() - 1].to_owned();
+                return Ok(Expression::unit(stem).pow(digit.to_digit(10).unwrap() as Num));
+            }
+        }
+
+        Ok(Expression::unit(name))
+    }
+
+    /// Checks if the next word is `word`.
+    fn at_keyword(&self, word: &str) -> bool {
+        let end = self.index + word.len();
+        self.chars
+            .get(self.index..end)
+            .is_some_and(|x| x.iter().copied().eq(word.chars()))
+            && self
+                .chars
+                .get(end)
+                .is_none_or(|x| x.is_whitespace() || is_operator(*x))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(x) if x.is_whitespace()) {
+            self.index += 1;
+        }
+    }
+}
+
+fn is_operator(chr: char) -> bool {
+    matches!(chr, '*' | '/' | '^' | '|' | '(' | ')' | '+' | '-')
+}
+
+impl Expression {
+    fn one() -> Self {
+        Self {
+            factor: 1.0,
+            units: Vec::new(),
+        }
+    }
+
+    fn unit(name: String) -> Self {
+        Self {
+            factor: 1.0,
+            units: vec![(name, 1.0)],
+        }
+    }
+
+    fn mul(mut self, rhs: Self) -> Self {
+        self.factor *= rhs.factor;
+        self.units.extend(rhs.units);
+        self
+    }
+
+    fn pow(mut self, power: Num) -> Self {
+        self.factor = self.factor.powf(power);
+        self.units.iter_mut().for_each(|x| x.1 *= power);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{parse, Expression};
+    use crate::{dimension::Dimensions, registry::UnitRegistry};
+
+    const UNITS: &str = r#"
+# Primitive units
+meter           !
+kg              !
+s               !
+bit             !
+
+# Prefixes
+kilo-           1e3
+u-              micro
+kibi-           2^10
+zilch-          0
+neg-            -1e3
+
+# Units
+inch            2.54 cm
+ft              12 inch   # already defined
+handspan        4 inch
+furlong         660 ft
+fortnight       14 day
+furlongpersec   furlong / s
+fff             furlong per fortnight
+sthene          1e3 kg m \
+                / s^2
+slinch          lbf s^2 / inch
+cubit           1|4 fathom
+fathom          6 ft
+dmcube          dm3
+rayl            kg / m^2 s
+
+tempF(x)        units=[1;K] (x+(-32)) degF + stdtemp ; (tempF+(-stdtemp))/degF + 32
+gasmark[degR]   1 735
+!unitlist       metric m;s
+
+!locale en_GB
+locunit         1000
+!endlocale
+!locale en_US
+locunit         0.001 inch
+!endlocale
+
+!set UNITS_TEST_SYSTEM yes
+!var UNITS_TEST_SYSTEM yes
+yesunit         2 m
+!endvar
+!varnot UNITS_TEST_SYSTEM yes
+nounit          3 m
+!endvar
+"#;
+
+    #[test]
+    fn test_parse() {
+        let units = |x: &str| parse(x, &|_| false).unwrap();
+        let unit = |name: &str, power| (name.to_owned(), power);
+
+        assert_eq!(
+            units("1|3 kg m/s^2 K"),
+            Expression {
+                factor: 1.0 / 3.0,
+                units: vec![
+                    unit("kg", 1.0),
+                    unit("m", 1.0),
+                    unit("s", -2.0),
+                    unit("K", -1.0)
+                ]
+            }
+        );
+        assert_eq!(units("cm3").units, [unit("cm", 3.0)]);
+        assert_eq!(units("(m/s)**2").units, [unit("m", 2.0), unit("s", -2.0)]);
+        assert_eq!(units("/s").units, [unit("s", -1.0)]);
+        assert_eq!(units("sqrt(m)").units, [unit("m", 0.5)]);
+        assert_relative_eq!(units("1.5e-3 m").factor, 1.5e-3);
+        assert!(parse("m + ft", &|_| false).is_err());
+        assert!(parse("exp(m)", &|_| false).is_err());
+    }
+
+    #[test]
+    fn test_import() {
+        let mut registry = UnitRegistry::new();
+        let report = registry.import_gnu_units(UNITS);
+
+        let skipped = report.skipped.iter().map(|x| x.line).collect::<Vec<_>>();
+        assert_eq!(skipped, [6, 11, 12, 13, 31, 32, 33]);
+        assert_eq!(report.prefixes, ["u"]);
+        assert!(report.existing.contains(&"ft".to_owned()));
+        assert!(report.units.contains(&"cubit".to_owned()));
+
+        let convert = |from: &str, to: &str| {
+            let from = Dimensions::parse_with(from, &registry).unwrap();
+            let to = Dimensions::parse_with(to, &registry).unwrap();
+            from.convert(&to, 1.0, false).unwrap()
+        };
+        assert_relative_eq!(convert("handspan", "cm"), 10.16);
+        assert_relative_eq!(convert("cubit", "inch"), 18.0);
+        assert_relative_eq!(convert("sthene", "N"), 1000.0);
+        assert_relative_eq!(convert("dmcube", "cm^3"), 1000.0);
+        assert_relative_eq!(convert("urayl", "Pa*s/m"), 1e-6);
+        assert!(Dimensions::parse_with("uhandspan", &registry).is_err());
+        assert_relative_eq!(convert("locunit", "mm"), 0.0254);
+        assert_relative_eq!(convert("yesunit", "m"), 2.0);
+        assert_relative_eq!(convert("fff", "furlongpersec"), 1.0 / 1_209_600.0);
+        assert!(Dimensions::parse_with("nounit", &registry).is_err());
+    }
+}
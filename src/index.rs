@@ -0,0 +1,121 @@
+//! Hash maps for looking up the units of a [`UnitRegistry`](crate::registry::UnitRegistry) by name.
+
+use hashbrown::HashMap;
+
+use crate::{prefix::Prefix, units::ConversionType};
+
+/// Where a unit comes in the lookup order: units, then derived units, then shorthands, each in the order they were added.
+type Rank = (u8, usize);
+
+/// The names of a registry's units, with the same precedence as looking through them in order.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UnitIndex {
+    /// Aliases and shorthand names, which are matched exactly.
+    exact: HashMap<&'static str, (Rank, ConversionType)>,
+    /// Unit and derived unit names, which are matched ignoring ASCII case.
+    folded: HashMap<&'static str, (Rank, ConversionType)>,
+    /// Every prefix a lowercase name could start with, in the order the prefixes are tried.
+    /// Only metric units are added, and each split still has to be checked.
+    prefixed: HashMap<String, Vec<Split>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Split {
+    /// The position of the prefix, then whether it is the symbol instead of the name.
+    rank: (usize, bool),
+    prefix: &'static Prefix,
+}
+
+impl Split {
+    fn text(&self) -> &'static str {
+        match self.rank.1 {
+            true => self.prefix.symbol,
+            false => self.prefix.name,
+        }
+    }
+}
+
+impl UnitIndex {
+    /// Adds a unit ranked after every unit of the same kind added before it.
+    pub fn add(&mut self, unit: ConversionType, position: usize, prefixes: &[&'static Prefix]) {
+        let (kind, folded, exact): (_, Option<&'static str>, &[&'static str]) = match unit {
+            ConversionType::Conversion(x) => (0, Some(x.name), x.aliases),
+            ConversionType::DerivedConversion(x) => (1, Some(x.name), x.aliases),
+            ConversionType::Shorthand(x) => (2, None, std::slice::from_ref(&x.name)),
+        };
+
+        let rank = (kind, position);
+        let insert = |map: &mut HashMap<_, (Rank, ConversionType)>, name| {
+            let entry = map.entry(name).or_insert((rank, unit.clone()));
+            if rank < entry.0 {
+                *entry = (rank, unit.clone());
+            }
+        };
+        folded.into_iter().for_each(|x| insert(&mut self.folded, x));
+        exact.iter().for_each(|&x| insert(&mut self.exact, x));
+
+        if unit.is_metric() {
+            for name in folded.iter().chain(exact) {
+                for (i, &prefix) in prefixes.iter().enumerate() {
+                    self.add_splits(name, i, prefix);
+                }
+            }
+        }
+    }
+
+    /// Adds a prefix tried after every prefix added before it.
+    pub fn add_prefix(&mut self, prefix: &'static Prefix, position: usize) {
+        let names = (self.folded.iter())
+            .chain(&self.exact)
+            .filter(|x| x.1 .1.is_metric())
+            .map(|x| *x.0)
+            .collect::<Vec<_>>();
+
+        for name in names {
+            self.add_splits(name, position, prefix);
+        }
+    }
+
+    fn add_splits(&mut self, name: &str, position: usize, prefix: &'static Prefix) {
+        for symbol in [false, true] {
+            let split = Split {
+                rank: (position, symbol),
+                prefix,
+            };
+
+            let key = format!("{}{name}", split.text()).to_ascii_lowercase();
+            let splits = self.prefixed.entry(key).or_default();
+            if let Err(i) = splits.binary_search_by_key(&split.rank, |x| x.rank) {
+                splits.insert(i, split);
+            }
+        }
+    }
+
+    /// Finds a unit, derived unit or shorthand by name or alias.
+    pub fn find(&self, s: &str) -> Option<ConversionType> {
+        let folded = match s.bytes().any(|x| x.is_ascii_uppercase()) {
+            true => self.folded.get(s.to_ascii_lowercase().as_str()),
+            false => self.folded.get(s),
+        };
+
+        [self.exact.get(s), folded]
+            .into_iter()
+            .flatten()
+            .min_by_key(|x| x.0)
+            .map(|x| x.1.clone())
+    }
+
+    /// The ways `s` can be split into a prefix and the rest, in the order they should be tried.
+    pub fn splits<'a>(
+        &'a self,
+        s: &'a str,
+    ) -> impl Iterator<Item = (&'static Prefix, &'a str)> + 'a {
+        let splits = match s.bytes().any(|x| x.is_ascii_uppercase()) {
+            true => self.prefixed.get(s.to_ascii_lowercase().as_str()),
+            false => self.prefixed.get(s),
+        };
+
+        (splits.into_iter().flatten())
+            .filter_map(move |x| Some((x.prefix, s.strip_prefix(x.text())?)))
+    }
+}
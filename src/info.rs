@@ -0,0 +1,162 @@
+//! Everything known about a single unit, as shown by the `info` subcommand.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    catalog::{unit_name, CatalogUnit, UnitKind},
+    dimension::Dimensions,
+    prefix::Prefix,
+    registry::UnitRegistry,
+    suggest,
+    units::{Conversion, Space, Transform},
+    Num,
+};
+
+#[derive(Debug, Clone)]
+pub struct UnitInfo {
+    /// The name that was looked up, like `km`.
+    pub query: String,
+    /// The prefix that was stripped from the name to find the unit.
+    pub prefix: Option<&'static Prefix>,
+    pub unit: CatalogUnit,
+    /// The value of one of the looked up unit in the base units of its spaces.
+    pub factor: Num,
+    /// The base units the factor is in, like `gram*meter*second^-2`.
+    pub base: String,
+}
+
+impl UnitInfo {
+    /// Looks up a unit, derived unit or shorthand by name or alias, with an optional metric prefix.
+    pub fn new(name: &str, registry: &UnitRegistry) -> Result<Self> {
+        let name = name.trim();
+        let (conversion, prefix) = registry
+            .get(name)
+            .or_else(|| registry.get(&name.replace(['_', '-'], " ")))
+            .with_context(|| format!("Unknown unit `{name}`{}", suggest::hint(name, registry)))?;
+
+        let sci_exponent = prefix.map_or(0.0, |x| x.power as Num);
+        let dimensions = Dimensions::from_conversion(conversion.clone(), sci_exponent)?;
+        let base = dimensions
+            .simplify()
+            .units()
+            .iter()
+            .filter(|x| !x.is_special())
+            .map(|x| {
+                let space = x.conversion().space;
+                let base = base_unit(space, registry)
+                    .map_or_else(|| space.to_string(), |x| x.name.to_owned());
+                unit_name(&base, x.power())
+            })
+            .collect::<Vec<_>>()
+            .join("*");
+
+        Ok(Self {
+            query: name.to_owned(),
+            prefix,
+            unit: CatalogUnit::new(conversion)?,
+            factor: dimensions
+                .units()
+                .iter()
+                .fold(1.0, |x, unit| unit.to_base(x)),
+            base,
+        })
+    }
+}
+
+/// Finds the unit the other units of a space are converted through, like meter or gram.
+fn base_unit(space: Space, registry: &UnitRegistry) -> Option<&'static Conversion> {
+    registry
+        .units()
+        .iter()
+        .copied()
+        .find(|&x| x.space == space && !x.special && x.transform == Transform::scale(1.0))
+}
+
+impl Display for UnitInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = &self.unit;
+        match self.prefix {
+            Some(prefix) => writeln!(
+                f,
+                "{} ({} with the prefix {}, 10^{})",
+                self.query, unit.name, prefix.name, prefix.power
+            )?,
+            None if self.query != unit.name => writeln!(f, "{} ({})", self.query, unit.name)?,
+            None => writeln!(f, "{}", unit.name)?,
+        }
+
+        let kind = match unit.kind {
+            UnitKind::Unit => "unit",
+            UnitKind::Derived => "derived unit",
+            UnitKind::Shorthand => "shorthand",
+        };
+        writeln!(
+            f,
+            "Kind: {kind}{}",
+            if unit.metric { ", metric" } else { "" }
+        )?;
+        writeln!(f, "Space: {}", unit.space)?;
+        if !unit.aliases.is_empty() {
+            writeln!(f, "Aliases: {}", unit.aliases.join(", "))?;
+        }
+        if let Some(description) = &unit.description {
+            writeln!(f, "Description: {description}")?;
+        }
+        if let Some(link) = &unit.link {
+            writeln!(f, "Link: {link}")?;
+        }
+        if let Some(definition) = &unit.definition {
+            writeln!(f, "Expands to: {definition}")?;
+        }
+
+        write!(
+            f,
+            "Definition: 1 {} = {} {}",
+            self.query, self.factor, self.base
+        )?;
+        if unit.factor.is_none() || unit.offset.is_some() {
+            write!(f, " (not a multiple of the base unit)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::UnitInfo;
+    use crate::{catalog::UnitKind, registry::UnitRegistry};
+
+    #[test]
+    fn test_info() {
+        let registry = UnitRegistry::builtin();
+
+        let info = UnitInfo::new("km", registry).unwrap();
+        assert_eq!(info.prefix.unwrap().name, "kilo");
+        assert_eq!(info.unit.name, "meter");
+        assert_relative_eq!(info.factor, 1000.0);
+        assert_eq!(info.base, "meter");
+
+        let info = UnitInfo::new("N", registry).unwrap();
+        assert_eq!(info.unit.kind, UnitKind::Derived);
+        assert_relative_eq!(info.factor, 1000.0);
+        assert_eq!(info.base, "gram*meter*second^-2");
+
+        let info = UnitInfo::new("mph", registry).unwrap();
+        assert_eq!(info.unit.kind, UnitKind::Shorthand);
+        assert_relative_eq!(info.factor, 0.44704);
+        assert_eq!(info.base, "meter*second^-1");
+
+        let info = UnitInfo::new("statute_mile", registry).unwrap();
+        assert_eq!(info.unit.name, "statutemile");
+
+        let info = UnitInfo::new("kip", registry).unwrap();
+        assert_eq!(info.unit.link, None);
+        assert!(!info.to_string().contains("Link:"));
+
+        assert!(UnitInfo::new("notaunit", registry).is_err());
+    }
+}
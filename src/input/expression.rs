@@ -0,0 +1,463 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+
+use super::number::{Literal, NumberLexer};
+use crate::{dimension::Dimensions, registry::UnitRegistry, units::Space, Num};
+
+/// The left side of an input, a quantity or arithmetic on quantities.
+/// For example `3 ft + 4 in`, `(10 km / 2 h)` or `5 ft 3 in`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Quantity {
+        value: Literal,
+        unit: String,
+    },
+    /// Quantities of the same dimension written one after another, like `1h20m5s`.
+    /// Only contains [`Expression::Quantity`]s.
+    Compound(Vec<Expression>),
+    Operation(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// The result of evaluating an [`Expression`], a value in some unit.
+#[derive(Debug, Clone)]
+pub struct Quantity {
+    pub value: Num,
+    pub dimensions: Dimensions,
+}
+
+pub struct ExpressionParser {
+    chars: Box<[char]>,
+    index: usize,
+}
+
+impl Expression {
+    /// Evaluates the expression.
+    /// Added and subtracted quantities must have matching dimensions and are converted to the unit of the left side.
+    pub fn evaluate(&self) -> Result<Quantity> {
+        self.evaluate_with(UnitRegistry::builtin())
+    }
+
+    /// Evaluates the expression, looking up units in `registry`.
+    pub fn evaluate_with(&self, registry: &UnitRegistry) -> Result<Quantity> {
+        let (op, left, right) = match self {
+            Expression::Quantity { value, unit } => {
+                return Ok(Quantity {
+                    value: value.value,
+                    dimensions: Dimensions::parse_with(unit, registry)?,
+                })
+            }
+            Expression::Compound(parts) => return Self::evaluate_compound(parts, registry),
+            Expression::Operation(op, left, right) => (
+                op,
+                left.evaluate_with(registry)?,
+                right.evaluate_with(registry)?,
+            ),
+        };
+
+        Ok(match op {
+            Operator::Add | Operator::Sub => {
+                if left.dimensions != right.dimensions {
+                    bail!(
+                        "Cannot {} quantities with different dimensions. ({:#} vs {:#})",
+                        if *op == Operator::Add {
+                            "add"
+                        } else {
+                            "subtract"
+                        },
+                        left.dimensions.simplify(),
+                        right.dimensions.simplify()
+                    );
+                }
+
+                let right = right
+                    .dimensions
+                    .convert(&left.dimensions, right.value, false)?;
+                Quantity {
+                    value: if *op == Operator::Add {
+                        left.value + right
+                    } else {
+                        left.value - right
+                    },
+                    dimensions: left.dimensions,
+                }
+            }
+            Operator::Mul => Quantity {
+                value: left.value * right.value,
+                dimensions: left.dimensions * right.dimensions,
+            },
+            Operator::Div => Quantity {
+                value: left.value / right.value,
+                dimensions: left.dimensions / right.dimensions,
+            },
+        })
+    }
+
+    /// Sums the parts of a compound quantity in the unit of the first part.
+    /// A negative first part negates the whole quantity, so `-1 h 30 min` is -90 minutes.
+    fn evaluate_compound(parts: &[Expression], registry: &UnitRegistry) -> Result<Quantity> {
+        let mut total = parts[0].evaluate_with(registry)?;
+        let space = total.dimensions.space();
+        let sign = total.value.signum();
+
+        for part in &parts[1..] {
+            let Expression::Quantity { value, unit } = part else {
+                unreachable!("Compound quantities only contain quantities")
+            };
+
+            let unit = space
+                .and_then(|space| compound_alias(space, unit))
+                .unwrap_or(unit);
+            let dimensions = Dimensions::parse_with(unit, registry)?;
+            if dimensions != total.dimensions {
+                bail!(
+                    "Mixed dimensions in compound quantity `{}`. (`{unit}` is {:#}, expected {:#})",
+                    Expression::Compound(parts.to_vec()),
+                    dimensions.simplify(),
+                    total.dimensions.simplify()
+                );
+            }
+
+            total.value += sign * dimensions.convert(&total.dimensions, value.value, false)?;
+        }
+
+        Ok(total)
+    }
+
+    /// The lowest significant figures of any number in the expression.
+    /// Each part of a compound quantity adds to its resolution, so their figures are summed.
+    pub fn significant_figures(&self) -> usize {
+        match self {
+            Expression::Quantity { value, .. } => value.significant_figures,
+            Expression::Compound(parts) => parts.iter().map(|x| x.significant_figures()).sum(),
+            Expression::Operation(_, left, right) => {
+                left.significant_figures().min(right.significant_figures())
+            }
+        }
+    }
+
+    /// Writes the expression back out, formatting each number with `format`.
+    pub fn format(&self, format: &impl Fn(Num) -> String) -> String {
+        match self {
+            Expression::Quantity { value, unit } if unit.is_empty() => format(value.value),
+            Expression::Quantity { value, unit } => format!("{} {unit}", format(value.value)),
+            Expression::Compound(parts) => parts
+                .iter()
+                .map(|x| x.format(format))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Expression::Operation(op, left, right) => {
+                let wrap = |child: &Expression, strict: bool| match child {
+                    Expression::Operation(child_op, ..)
+                        if child_op.precedence() < op.precedence()
+                            || (strict && child_op.precedence() == op.precedence()) =>
+                    {
+                        format!("({})", child.format(format))
+                    }
+                    _ => child.format(format),
+                };
+
+                let strict = matches!(op, Operator::Sub | Operator::Div);
+                format!("{} {op} {}", wrap(left, false), wrap(right, strict))
+            }
+        }
+    }
+}
+
+/// Units that mean something else after a larger unit in a compound quantity, like the `m` in `1h20m` or the `'` in `12° 30'`.
+pub(super) fn compound_alias(space: Space, unit: &str) -> Option<&'static str> {
+    Some(match (space, unit) {
+        (Space::Time, "m") => "min",
+        (Space::Angle, "'") => "arcmin",
+        (Space::Angle, "\"") => "arcsec",
+        _ => return None,
+    })
+}
+
+impl Operator {
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+        }
+    }
+}
+
+impl ExpressionParser {
+    pub fn parse(raw: &str) -> Result<Expression> {
+        let mut ctx = Self {
+            chars: raw.chars().collect(),
+            index: 0,
+        };
+
+        let expression = ctx.expression()?;
+        ctx.skip_whitespace();
+        if let Some(chr) = ctx.peek(0) {
+            bail!("Unexpected `{chr}` at position {}", ctx.index + 1);
+        }
+
+        Ok(expression)
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<Expression> {
+        let mut left = self.term()?;
+
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek(0) {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Sub,
+                _ => return Ok(left),
+            };
+
+            self.index += 1;
+            let right = self.term()?;
+            left = Expression::Operation(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// `factor (('*' | '/') factor)*`
+    fn term(&mut self) -> Result<Expression> {
+        let mut left = self.factor()?;
+
+        loop {
+            self.skip_whitespace();
+            let op = match self.peek(0) {
+                Some('*') => Operator::Mul,
+                Some('/') => Operator::Div,
+                _ => return Ok(left),
+            };
+
+            self.index += 1;
+            let right = self.factor()?;
+            left = Expression::Operation(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    /// `'(' expression ')' | quantity quantity*`
+    fn factor(&mut self) -> Result<Expression> {
+        self.skip_whitespace();
+
+        if self.peek(0) == Some('(') {
+            self.index += 1;
+            let expression = self.expression()?;
+            self.skip_whitespace();
+            if self.peek(0) != Some(')') {
+                bail!("Expected `)` at position {}", self.index + 1);
+            }
+
+            self.index += 1;
+            return Ok(expression);
+        }
+
+        let mut parts = vec![self.quantity()?];
+        loop {
+            self.skip_whitespace();
+            if !self.starts_number(0) {
+                break;
+            }
+
+            parts.push(self.quantity()?);
+        }
+
+        Ok(match parts.len() {
+            1 => parts.pop().unwrap(),
+            _ => Expression::Compound(parts),
+        })
+    }
+
+    /// `number unit?`
+    fn quantity(&mut self) -> Result<Expression> {
+        let (value, len) = NumberLexer::lex(&self.chars[self.index..])?;
+        self.index += len;
+        let unit = self.unit();
+
+        Ok(Expression::Quantity { value, unit })
+    }
+
+    /// Reads a unit up to the next operator between quantities or the next number that isn't an exponent.
+    /// Operators count as part of the unit unless followed by a number or group, so `10 m/s` is one quantity, but `10 m / 2 s` is two.
+    fn unit(&mut self) -> String {
+        let start = self.index;
+        let mut depth = 0;
+        let mut last = None;
+        let mut previous = None;
+
+        while let Some(chr) = self.peek(0) {
+            match chr {
+                // Digits directly after a digit or after an operator are part of an exponent, like `m^2` or `Hz^0.5`.
+                _ if depth == 0
+                    && self.starts_number(0)
+                    && !matches!(previous, Some('0'..='9' | '.'))
+                    && !matches!(last, Some('^' | '*' | '/' | '-' | '(')) =>
+                {
+                    break
+                }
+                '(' => depth += 1,
+                ')' if depth == 0 => break,
+                ')' => depth -= 1,
+                // Allows negative exponents, like `m^-2`.
+                '-' if self.index > start && self.chars[self.index - 1] == '^' => {}
+                '+' | '-' | '*' | '/' if depth == 0 && self.starts_operand(1) => break,
+                _ => {}
+            }
+
+            if !chr.is_whitespace() {
+                last = Some(chr);
+            }
+            previous = Some(chr);
+            self.index += 1;
+        }
+
+        self.chars[start..self.index]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_owned()
+    }
+
+    /// Checks if an operand (a number or an opening parenthesis before a number) starts after `offset` and any whitespace.
+    fn starts_operand(&self, mut offset: usize) -> bool {
+        while self.peek(offset).is_some_and(|c| c.is_whitespace()) {
+            offset += 1;
+        }
+
+        match self.peek(offset) {
+            Some('(') => self.starts_operand(offset + 1),
+            Some('0'..='9') => true,
+            Some('.' | '-' | '+') => self
+                .peek(offset + 1)
+                .is_some_and(|c| c.is_ascii_digit() || c == '.'),
+            _ => false,
+        }
+    }
+
+    /// Checks if an unsigned number starts at `offset`.
+    fn starts_number(&self, offset: usize) -> bool {
+        match self.peek(offset) {
+            Some('0'..='9') => true,
+            Some('.') => self.peek(offset + 1).is_some_and(|c| c.is_ascii_digit()),
+            _ => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek(0).is_some_and(|c| c.is_whitespace()) {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "/",
+        })
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(&|x| x.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expression, ExpressionParser, Operator};
+
+    fn unit(expression: &Expression) -> &str {
+        match expression {
+            Expression::Quantity { unit, .. } => unit,
+            _ => panic!("Not a quantity"),
+        }
+    }
+
+    #[test]
+    fn test_parse_single() {
+        let exp = ExpressionParser::parse("10 m/s^-2").unwrap();
+        assert_eq!(unit(&exp), "m/s^-2");
+
+        let exp = ExpressionParser::parse("10 m/(s*s)").unwrap();
+        assert_eq!(unit(&exp), "m/(s*s)");
+    }
+
+    #[test]
+    fn test_parse_operations() {
+        let Expression::Operation(Operator::Add, left, right) =
+            ExpressionParser::parse("3 ft + 4 in").unwrap()
+        else {
+            panic!("Expected addition");
+        };
+        assert_eq!((unit(&left), unit(&right)), ("ft", "in"));
+
+        let exp = ExpressionParser::parse("(10 km / 2 h) * 3 h - 1 km").unwrap();
+        assert_eq!(exp.to_string(), "10 km / 2 h * 3 h - 1 km");
+
+        let exp = ExpressionParser::parse("1 m - (2 m - 3 m)").unwrap();
+        assert_eq!(exp.to_string(), "1 m - (2 m - 3 m)");
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let exp = ExpressionParser::parse("3 ft + 4 in")
+            .unwrap()
+            .evaluate()
+            .unwrap();
+        assert!((exp.value - 3.333).abs() < 0.001);
+
+        let exp = ExpressionParser::parse("10 km / 2 h")
+            .unwrap()
+            .evaluate()
+            .unwrap();
+        assert_eq!(exp.value, 5.0);
+
+        assert!(ExpressionParser::parse("3 ft + 4 s")
+            .unwrap()
+            .evaluate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        let exp = ExpressionParser::parse("1h20m5s").unwrap();
+        assert_eq!(exp.to_string(), "1 h 20 m 5 s");
+        assert_eq!(
+            exp.evaluate().unwrap().value,
+            1.0 + 20.0 / 60.0 + 5.0 / 3600.0
+        );
+
+        let exp = ExpressionParser::parse("5 ft 3 in + 2 in").unwrap();
+        assert_eq!(exp.to_string(), "5 ft 3 in + 2 in");
+        assert!(matches!(
+            exp,
+            Expression::Operation(Operator::Add, left, _) if matches!(*left, Expression::Compound(..))
+        ));
+
+        let exp = ExpressionParser::parse("1 m^2 3 m^-2").unwrap();
+        assert_eq!(exp.to_string(), "1 m^2 3 m^-2");
+        assert!(exp.evaluate().is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(ExpressionParser::parse("(3 ft + 4 in").is_err());
+        assert!(ExpressionParser::parse("3 ft + (4 in").is_err());
+    }
+}
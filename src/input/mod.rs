@@ -0,0 +1,99 @@
+use std::{result, str::FromStr};
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::registry::UnitRegistry;
+
+use self::{
+    expression::{Expression, ExpressionParser, Quantity},
+    number::NumberLexer,
+    target::{Part, Target},
+};
+
+pub mod expression;
+pub mod number;
+pub mod target;
+
+static SEPARATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"=>|->|\sto\s").unwrap());
+
+#[derive(Debug)]
+pub struct Input {
+    /// The quantity to convert, or arithmetic on quantities.
+    pub from: Expression,
+    /// The units to convert to, separated by commas, like `=> km, m, ft`.
+    pub to: Vec<Target>,
+}
+
+impl Input {
+    /// Parses an input with [UCUM](crate::ucum) codes as units, like `120 mm[Hg] => kPa, [psi]`.
+    /// UCUM codes use `.`, `/` and numbers in units, so arithmetic and compound quantities are not supported.
+    /// Use a registry with [`UnitSyntax::Ucum`](crate::registry::UnitSyntax::Ucum) to convert it.
+    pub fn parse_ucum(inp: &str) -> Result<Self> {
+        let (from, to) = split(inp)?;
+        let chars = from.trim().chars().collect::<Vec<_>>();
+        let (value, len) = NumberLexer::lex(&chars)?;
+
+        Ok(Input {
+            from: Expression::Quantity {
+                value,
+                unit: chars[len..].iter().collect::<String>().trim().to_owned(),
+            },
+            to: to
+                .split(',')
+                .map(|x| Target::Unit(x.trim().to_owned()))
+                .collect(),
+        })
+    }
+
+    /// Evaluates the input and converts it to every target.
+    pub fn convert(&self, debug: bool) -> Result<Vec<Result<Vec<Part>>>> {
+        self.convert_with(UnitRegistry::builtin(), debug)
+    }
+
+    /// Evaluates the input and converts it to every target, looking up units in `registry`.
+    pub fn convert_with(
+        &self,
+        registry: &UnitRegistry,
+        debug: bool,
+    ) -> Result<Vec<Result<Vec<Part>>>> {
+        let from = self.from.evaluate_with(registry)?;
+        Ok(self.convert_all(&from, registry, debug))
+    }
+
+    /// Converts an already evaluated input to every target.
+    /// Each target is checked and converted on its own, so one bad target doesn't stop the others.
+    pub fn convert_all(
+        &self,
+        from: &Quantity,
+        registry: &UnitRegistry,
+        debug: bool,
+    ) -> Vec<Result<Vec<Part>>> {
+        self.to
+            .iter()
+            .map(|target| target.convert_with(from, registry, debug))
+            .collect()
+    }
+}
+
+impl FromStr for Input {
+    type Err = anyhow::Error;
+
+    fn from_str(inp: &str) -> result::Result<Self, Self::Err> {
+        let (from, to) = split(inp)?;
+        Ok(Input {
+            from: ExpressionParser::parse(from)?,
+            to: Target::parse_list(to)?,
+        })
+    }
+}
+
+/// Splits an input into the quantity and the targets.
+fn split(inp: &str) -> Result<(&str, &str)> {
+    let mut parts = SEPARATOR.split(inp);
+    Ok((
+        parts.next().unwrap(),
+        parts.next().context("No separator found.")?,
+    ))
+}
@@ -0,0 +1,326 @@
+use anyhow::{anyhow, Result};
+
+use crate::{format, Num};
+
+/// A number literal read from the start of an input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Literal {
+    pub value: Num,
+    /// The significant figures of the literal as written.
+    /// Fractions and non-decimal literals use the figures of their decimal value.
+    pub significant_figures: usize,
+}
+
+/// Reads number literals.
+/// Supports signs, decimals, exponents (`1.5e-3`), digit separators (`1_000`, `1,000`),
+/// fractions (`3/4`), mixed numbers (`1 1/2`) and hex, octal and binary literals (`0x1F`, `0o17`, `0b1010`).
+pub struct NumberLexer<'a> {
+    chars: &'a [char],
+    index: usize,
+}
+
+impl<'a> NumberLexer<'a> {
+    /// Reads a number literal from the start of `chars`.
+    /// Returns the literal and the number of chars it took up.
+    pub fn lex(chars: &'a [char]) -> Result<(Literal, usize)> {
+        let mut ctx = Self { chars, index: 0 };
+        let literal = ctx.literal()?;
+        Ok((literal, ctx.index))
+    }
+
+    fn literal(&mut self) -> Result<Literal> {
+        let negative = match self.peek(0) {
+            Some(sign @ ('-' | '+')) => {
+                self.index += 1;
+                sign == '-'
+            }
+            _ => false,
+        };
+        let sign = if negative { -1.0 } else { 1.0 };
+
+        if let Some(radix) = self.radix_prefix() {
+            self.index += 2;
+            let value = self.radix_digits(radix)?;
+            return Ok(exact(sign * value));
+        }
+
+        let start = self.index;
+        let whole = self.decimal()?;
+        let is_integer = self.chars[start..self.index]
+            .iter()
+            .all(|c| !matches!(c, '.' | 'e' | 'E'));
+
+        if is_integer {
+            // `3/4`
+            if let Some(fraction) = self.fraction()? {
+                return Ok(exact(sign * whole.value * fraction));
+            }
+
+            // `1 1/2`
+            let end = self.index;
+            while self.peek(0).is_some_and(|c| c.is_whitespace()) {
+                self.index += 1;
+            }
+
+            if self.index > end && self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+                let checkpoint = self.index;
+                let numerator = self.decimal()?;
+                if self.chars[checkpoint..self.index]
+                    .iter()
+                    .all(char::is_ascii_digit)
+                {
+                    if let Some(fraction) = self.fraction()? {
+                        return Ok(exact(sign * (whole.value + numerator.value * fraction)));
+                    }
+                }
+            }
+            self.index = end;
+        }
+
+        Ok(Literal {
+            value: sign * whole.value,
+            ..whole
+        })
+    }
+
+    /// Reads an unsigned decimal number with an optional exponent.
+    fn decimal(&mut self) -> Result<Literal> {
+        let mut digits = String::new();
+        self.digits(&mut digits)?;
+
+        if self.peek(0) == Some('.') {
+            self.index += 1;
+            digits.push('.');
+            self.digits(&mut digits)?;
+        }
+
+        if !digits.chars().any(|c| c.is_ascii_digit()) {
+            return Err(self.unexpected("Expected a number"));
+        }
+
+        // Only treat `e` as an exponent if digits follow, so units like `eV` still work.
+        if let Some('e' | 'E') = self.peek(0) {
+            let sign = matches!(self.peek(1), Some('-' | '+')) as usize;
+            if self.peek(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                digits.extend(&self.chars[self.index..self.index + 1 + sign]);
+                self.index += 1 + sign;
+                self.digits(&mut digits)?;
+            }
+        }
+
+        if self.peek(0) == Some('.') {
+            return Err(self.unexpected("Unexpected character"));
+        }
+
+        Ok(Literal {
+            value: digits.parse::<Num>()?,
+            significant_figures: format::significant_figures(&digits),
+        })
+    }
+
+    /// Reads a run of digits into `out`, dropping any digit separators.
+    fn digits(&mut self, out: &mut String) -> Result<()> {
+        while let Some(chr) = self.peek(0) {
+            match chr {
+                '0'..='9' => out.push(chr),
+                '_' if self.is_digit_at(-1) && self.is_digit_at(1) => {}
+                // Commas only separate groups of exactly three digits, `1,000` but not `1,00`.
+                ',' if self.is_digit_at(-1) => {
+                    if !(1..=3).all(|i| self.is_digit_at(i)) || self.is_digit_at(4) {
+                        return Err(self.unexpected("Invalid digit separator"));
+                    }
+                }
+                '_' => return Err(self.unexpected("Invalid digit separator")),
+                _ => break,
+            }
+            self.index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the `/4` of `3/4`, returning the reciprocal of the denominator.
+    fn fraction(&mut self) -> Result<Option<Num>> {
+        if self.peek(0) != Some('/') || !self.is_digit_at(1) {
+            return Ok(None);
+        }
+
+        self.index += 1;
+        let start = self.index;
+        let denominator = self.decimal()?;
+        if !self.chars[start..self.index]
+            .iter()
+            .all(char::is_ascii_digit)
+        {
+            self.index = start;
+            return Err(self.unexpected("Fraction denominators must be integers"));
+        }
+
+        if denominator.value == 0.0 {
+            self.index = start;
+            return Err(self.unexpected("Fraction denominator is zero"));
+        }
+
+        Ok(Some(1.0 / denominator.value))
+    }
+
+    /// Only counts as a prefix if a digit of the radix follows, so `0bar` is zero bar, not a binary literal.
+    fn radix_prefix(&self) -> Option<u32> {
+        if self.peek(0) != Some('0') {
+            return None;
+        }
+
+        let radix = match self.peek(1)? {
+            'x' | 'X' => 16,
+            'o' | 'O' => 8,
+            'b' | 'B' => 2,
+            _ => return None,
+        };
+        self.peek(2)
+            .is_some_and(|x| x.is_digit(radix))
+            .then_some(radix)
+    }
+
+    fn radix_digits(&mut self, radix: u32) -> Result<Num> {
+        let mut value: Num = 0.0;
+        let mut any = false;
+
+        while let Some(chr) = self.peek(0) {
+            if let Some(digit) = chr.to_digit(radix) {
+                value = value * radix as Num + digit as Num;
+                any = true;
+            } else if chr == '_' && any && self.peek(1).is_some_and(|c| c.is_digit(radix)) {
+                // Digit separator
+            } else if chr.is_ascii_digit() {
+                return Err(self.unexpected("Invalid digit for base"));
+            } else {
+                break;
+            }
+            self.index += 1;
+        }
+
+        if !any {
+            return Err(self.unexpected("Expected digits"));
+        }
+
+        Ok(value)
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn is_digit_at(&self, offset: isize) -> bool {
+        self.index
+            .checked_add_signed(offset)
+            .and_then(|i| self.chars.get(i))
+            .is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn unexpected(&self, message: &str) -> anyhow::Error {
+        let literal = self.chars[..self.chars.len().min(self.index + 1)]
+            .iter()
+            .collect::<String>();
+        match self.peek(0) {
+            Some(chr) => anyhow!(
+                "{message}: unexpected `{chr}` at position {} of `{literal}`",
+                self.index + 1
+            ),
+            None => anyhow!("{message}: unexpected end of input after `{literal}`"),
+        }
+    }
+}
+
+/// A literal without written precision, like a fraction or hex number.
+fn exact(value: Num) -> Literal {
+    Literal {
+        value,
+        significant_figures: format::significant_figures(&value.abs().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NumberLexer;
+
+    fn lex(raw: &str) -> (f64, String) {
+        let chars = raw.chars().collect::<Vec<_>>();
+        let (literal, len) = NumberLexer::lex(&chars).unwrap();
+        (literal.value, chars[len..].iter().collect())
+    }
+
+    fn lex_err(raw: &str) -> String {
+        let chars = raw.chars().collect::<Vec<_>>();
+        NumberLexer::lex(&chars).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(lex("10 m"), (10.0, " m".to_owned()));
+        assert_eq!(lex("-2.5E+3m"), (-2500.0, "m".to_owned()));
+        assert_eq!(lex("1e-3 s"), (0.001, " s".to_owned()));
+        assert_eq!(lex(".5 in"), (0.5, " in".to_owned()));
+    }
+
+    #[test]
+    fn test_unit_starting_with_e() {
+        assert_eq!(lex("5eV"), (5.0, "eV".to_owned()));
+        assert_eq!(lex("5e3eV"), (5000.0, "eV".to_owned()));
+        assert_eq!(lex("2 erg"), (2.0, " erg".to_owned()));
+    }
+
+    #[test]
+    fn test_separators() {
+        assert_eq!(lex("1_000 m"), (1000.0, " m".to_owned()));
+        assert_eq!(lex("1,000,000 m"), (1_000_000.0, " m".to_owned()));
+        assert_eq!(lex("1,000.5 m"), (1000.5, " m".to_owned()));
+    }
+
+    #[test]
+    fn test_fractions() {
+        assert_eq!(lex("3/4 in"), (0.75, " in".to_owned()));
+        assert_eq!(lex("1 1/2 cup"), (1.5, " cup".to_owned()));
+        assert_eq!(lex("-1 1/2 cup"), (-1.5, " cup".to_owned()));
+        assert_eq!(lex("10 m/s"), (10.0, " m/s".to_owned()));
+        assert_eq!(lex("10/s"), (10.0, "/s".to_owned()));
+    }
+
+    #[test]
+    fn test_radix() {
+        assert_eq!(lex("0x1F m"), (31.0, " m".to_owned()));
+        assert_eq!(lex("0b1010_1010 m"), (170.0, " m".to_owned()));
+        assert_eq!(lex("0o17m"), (15.0, "m".to_owned()));
+    }
+
+    #[test]
+    fn test_radix_letters_as_units() {
+        assert_eq!(lex("0bar"), (0.0, "bar".to_owned()));
+        assert_eq!(lex("0oz"), (0.0, "oz".to_owned()));
+        assert_eq!(lex("0xyz"), (0.0, "xyz".to_owned()));
+        assert_eq!(lex("0b"), (0.0, "b".to_owned()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            lex_err("1.2.3 m"),
+            "Unexpected character: unexpected `.` at position 4 of `1.2.`"
+        );
+        assert_eq!(
+            lex_err("1,00 m"),
+            "Invalid digit separator: unexpected `,` at position 2 of `1,`"
+        );
+        assert_eq!(
+            lex_err("0b102"),
+            "Invalid digit for base: unexpected `2` at position 5 of `0b102`"
+        );
+        assert_eq!(
+            lex_err("1/0 m"),
+            "Fraction denominator is zero: unexpected `0` at position 3 of `1/0`"
+        );
+        assert_eq!(
+            lex_err("m"),
+            "Expected a number: unexpected `m` at position 1 of `m`"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{bail, Result};
+
+use super::expression::{compound_alias, Quantity};
+use crate::{
+    auto::{self, UnitSystem},
+    dimension::Dimensions,
+    registry::UnitRegistry,
+    Num,
+};
+
+/// The right side of an input, the unit(s) to convert to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// A single unit, like `=> mi/h`.
+    Unit(String),
+    /// Units to split the value over, largest first, like `=> ft + in` or `=> h:min:s`.
+    Compound(Vec<String>),
+    /// Picks a readable unit, like `=> ?`, `=> auto` or `=> auto si`.
+    Auto(UnitSystem),
+}
+
+/// A value in one unit of a [`Target`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub value: Num,
+    pub unit: String,
+}
+
+impl Target {
+    /// Parses a comma separated list of targets, like `km, ft + in`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>> {
+        split_top_level(s, &[','])
+            .into_iter()
+            .map(|x| x.parse())
+            .collect()
+    }
+
+    /// The units of the target.
+    /// Automatic targets have none until resolved with [`Target::resolve`].
+    pub fn units(&self) -> &[String] {
+        match self {
+            Target::Unit(unit) => std::slice::from_ref(unit),
+            Target::Compound(units) => units,
+            Target::Auto(..) => &[],
+        }
+    }
+
+    /// Replaces an automatic target with the unit picked for the quantity.
+    pub fn resolve(&self, quantity: &Quantity, registry: &UnitRegistry) -> Result<Target> {
+        Ok(match self {
+            Target::Auto(system) => match auto::best_unit(quantity, *system, registry) {
+                Some(unit) => Target::Unit(unit),
+                None => bail!(
+                    "No known unit matches the dimensions {:#}",
+                    quantity.dimensions.simplify()
+                ),
+            },
+            _ => self.clone(),
+        })
+    }
+
+    /// Converts a quantity to the target.
+    /// Compound targets carry the whole part of each unit, leaving the remainder for the next, with the last unit taking what is left.
+    /// They also allow the same short units as compound quantities, like `d+h+m`.
+    pub fn convert(&self, quantity: &Quantity, debug: bool) -> Result<Vec<Part>> {
+        self.convert_with(quantity, UnitRegistry::builtin(), debug)
+    }
+
+    /// Converts a quantity to the target, looking up units in `registry`.
+    pub fn convert_with(
+        &self,
+        quantity: &Quantity,
+        registry: &UnitRegistry,
+        debug: bool,
+    ) -> Result<Vec<Part>> {
+        if let Target::Auto(..) = self {
+            return self
+                .resolve(quantity, registry)?
+                .convert_with(quantity, registry, debug);
+        }
+
+        let space = quantity.dimensions.space();
+        let units = self
+            .units()
+            .iter()
+            .map(|unit| {
+                let alias = match (self, space) {
+                    (Target::Compound(..), Some(space)) => compound_alias(space, unit),
+                    _ => None,
+                };
+                let dimensions = Dimensions::parse_with(alias.unwrap_or(unit), registry)?;
+                if dimensions != quantity.dimensions {
+                    bail!(
+                        "Unit dimensions do not match. ({:#} vs {:#})",
+                        quantity.dimensions.simplify(),
+                        dimensions.simplify()
+                    );
+                }
+                Ok((unit, dimensions))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let value = quantity
+            .dimensions
+            .convert(&units[0].1, quantity.value, debug)?;
+        let sign = value.signum();
+        let mut value = value.abs();
+        let mut parts = Vec::with_capacity(units.len());
+
+        for (i, (unit, dimensions)) in units.iter().enumerate() {
+            let Some((_, next)) = units.get(i + 1) else {
+                // Only remainders carry error from the parts before them, a plain conversion is left as is.
+                let value = match units.len() > 1 {
+                    true => snap(value),
+                    false => value,
+                };
+                parts.push(Part {
+                    value,
+                    unit: unit.to_string(),
+                });
+                break;
+            };
+
+            let whole = whole_part(value);
+            parts.push(Part {
+                value: whole,
+                unit: unit.to_string(),
+            });
+            value = dimensions.convert(next, (value - whole).max(0.0), debug)?;
+        }
+
+        parts[0].value *= sign;
+        Ok(parts)
+    }
+}
+
+impl Part {
+    /// Writes out the parts of a converted value, like `5 ft 3.2 in`.
+    /// Only the last part is formatted with `format`, the others are whole numbers.
+    pub fn format_all(parts: &[Part], format: &impl Fn(Num) -> String) -> String {
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                let value = if i + 1 == parts.len() {
+                    format(part.value)
+                } else {
+                    part.value.to_string()
+                };
+                format!("{value} {}", part.unit)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Rounds a value to the nearest whole number if floating point error left it just off one.
+/// The tolerance is relative, so small values are kept.
+fn snap(value: Num) -> Num {
+    if (value - value.round()).abs() < 1e-9 * value.abs() {
+        value.round()
+    } else {
+        value
+    }
+}
+
+/// Truncates a value, rounding up when floating point error left it just under a whole number.
+fn whole_part(value: Num) -> Num {
+    if (value - value.round()).abs() < 1e-9 * value.abs().max(1.0) {
+        value.round()
+    } else {
+        value.trunc()
+    }
+}
+
+impl FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let auto = s
+            .trim()
+            .strip_prefix('?')
+            .or_else(|| s.trim().strip_prefix("auto"));
+        if let Some(system) = auto {
+            return match UnitSystem::from_name(system.trim()) {
+                Some(system) => Ok(Target::Auto(system)),
+                None => bail!("Unknown unit system `{}`", system.trim()),
+            };
+        }
+
+        let mut units = split_top_level(s, &['+', ':']);
+        if units.len() > 1 && units.iter().any(|x| x.is_empty()) {
+            bail!("Empty unit in compound target `{s}`");
+        }
+
+        Ok(match units.len() {
+            1 => Target::Unit(units.pop().unwrap()),
+            _ => Target::Compound(units),
+        })
+    }
+}
+
+/// Splits a string on any of the separators outside of parentheses, trimming each part.
+fn split_top_level(s: &str, separators: &[char]) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, chr) in s.char_indices() {
+        match chr {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth == 0 && separators.contains(&chr) => {
+                parts.push(s[start..i].trim().to_owned());
+                start = i + chr.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(s[start..].trim().to_owned());
+    parts
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Auto(UnitSystem::Any) => f.write_str("auto"),
+            Target::Auto(UnitSystem::Si) => f.write_str("auto si"),
+            _ => f.write_str(&self.units().join(" + ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use approx::assert_relative_eq;
+
+    use super::{Part, Target};
+    use crate::auto::UnitSystem;
+    use crate::input::expression::ExpressionParser;
+
+    fn convert(from: &str, to: &str) -> String {
+        let quantity = ExpressionParser::parse(from).unwrap().evaluate().unwrap();
+        let parts = Target::from_str(to)
+            .unwrap()
+            .convert(&quantity, false)
+            .unwrap();
+        Part::format_all(&parts, &|x| format!("{x:.1}"))
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            Target::from_str("m/(s*s)").unwrap(),
+            Target::Unit("m/(s*s)".to_owned())
+        );
+        assert_eq!(
+            Target::from_str("h:min:s").unwrap(),
+            Target::Compound(vec!["h".to_owned(), "min".to_owned(), "s".to_owned()])
+        );
+        assert!(Target::from_str("ft + ").is_err());
+        assert_eq!(
+            Target::from_str("?").unwrap(),
+            Target::Auto(UnitSystem::Any)
+        );
+        assert_eq!(
+            Target::from_str("auto si").unwrap(),
+            Target::Auto(UnitSystem::Si)
+        );
+
+        assert_eq!(
+            Target::parse_list("km, ft + in").unwrap(),
+            vec![
+                Target::Unit("km".to_owned()),
+                Target::Compound(vec!["ft".to_owned(), "in".to_owned()])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compound_target() {
+        assert_eq!(convert("1.605 m", "ft + in"), "5 ft 3.2 in");
+        assert_eq!(convert("95820 s", "D+h+min"), "1 D 2 h 37.0 min");
+        assert_eq!(convert("95820 s", "d+h+m"), "1 d 2 h 37.0 m");
+        assert_eq!(convert("3600 s", "h:min:s"), "1 h 0 min 0.0 s");
+        assert_eq!(convert("-90 min", "h + min"), "-1 h 30.0 min");
+        assert_eq!(convert("0.00003 GHz", "?"), "30.0 kHz");
+    }
+
+    #[test]
+    fn test_compound_remainder() {
+        let quantity = ExpressionParser::parse("95820 s")
+            .unwrap()
+            .evaluate()
+            .unwrap();
+        let parts = Target::from_str("d+h+min")
+            .unwrap()
+            .convert(&quantity, false)
+            .unwrap();
+        assert_eq!(parts[2].value, 37.0);
+
+        let quantity = ExpressionParser::parse("1 eV").unwrap().evaluate().unwrap();
+        let parts = Target::from_str("J")
+            .unwrap()
+            .convert(&quantity, false)
+            .unwrap();
+        assert_relative_eq!(parts[0].value, 1.602_176_634e-19, max_relative = 1e-9);
+
+        let quantity = ExpressionParser::parse("1.0000000001 m")
+            .unwrap()
+            .evaluate()
+            .unwrap();
+        let parts = Target::from_str("m")
+            .unwrap()
+            .convert(&quantity, false)
+            .unwrap();
+        assert_eq!(parts[0].value, 1.0000000001);
+    }
+}
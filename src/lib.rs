@@ -4,13 +4,24 @@
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod args;
+pub mod auto;
+pub mod catalog;
+pub mod compatible;
+pub mod defs;
 pub mod dimension;
+pub mod format;
+pub mod gnu_units;
+mod index;
+pub mod info;
 pub mod input;
 pub mod misc;
 pub mod prefix;
+pub mod registry;
 pub mod shorthand;
+pub mod suggest;
 #[cfg(test)]
 mod test;
+pub mod ucum;
 pub mod units;
 
 type Num = f64;
@@ -2,31 +2,160 @@ use std::str::FromStr;
 
 use anyhow::{bail, Result};
 use clap::Parser;
-use thousands::Separable;
 
-use unit_convert::{args::Args, dimension::Dimensions, input};
+use unit_convert::{
+    args::{Args, Command},
+    catalog::{Catalog, CatalogUnit},
+    compatible::compatible,
+    defs,
+    info::UnitInfo,
+    input::{self, target::Part},
+    registry::{UnitRegistry, UnitSyntax},
+    ucum,
+};
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let inp = input::Input::from_str(&args.input)?;
+    let registry = load_registry(&args)?;
 
-    let from_dim = Dimensions::from_str(&inp.from_unit)?;
-    let to_dim = Dimensions::from_str(&inp.to_unit)?;
+    match &args.command {
+        #[cfg(feature = "serde")]
+        Some(Command::Export { format }) => {
+            println!("{}", Catalog::new(&registry)?.export(*format)?);
+            Ok(())
+        }
+        Some(Command::Info { unit }) => {
+            println!("{}", UnitInfo::new(unit, &registry)?);
+            Ok(())
+        }
+        Some(Command::List {
+            metric,
+            non_metric,
+            wacky,
+            no_wacky,
+        }) => {
+            let mut catalog = Catalog::new(&registry)?;
+            catalog.units.retain(|x| {
+                (!metric || x.metric)
+                    && (!non_metric || !x.metric)
+                    && (!wacky || x.wacky)
+                    && (!no_wacky || !x.wacky)
+            });
 
-    if from_dim != to_dim {
-        println!("{}\n{}", from_dim, to_dim);
-        bail!("Unit dimensions do not match.");
+            for (group, units) in catalog.groups() {
+                println!("{group}");
+                units.iter().for_each(|x| println!("  {}", list_entry(x)));
+            }
+            Ok(())
+        }
+        Some(Command::Compatible { unit }) => {
+            let found = compatible(unit, &registry)?;
+            if found.is_empty() {
+                bail!("No units are compatible with `{unit}`");
+            }
+
+            // There is no input value, so `--match-input` rounds to the digits a float can hold.
+            let precision = args.precision().resolve(f64::DIGITS as usize);
+            let format = |x| args.notation.format_with(x, precision);
+            for x in found {
+                let note = if x.offset { ", offset ignored" } else { "" };
+                println!(
+                    "1 {unit} = {} {}{note}",
+                    format(x.value),
+                    list_entry(&x.unit)
+                );
+            }
+            Ok(())
+        }
+        Some(Command::Search { query }) => {
+            let catalog = Catalog::new(&registry)?;
+            let found = catalog.search(query);
+            if found.is_empty() {
+                bail!("No units match `{query}`");
+            }
+
+            for unit in found {
+                println!("{} [{}]", list_entry(unit), unit.group);
+            }
+            Ok(())
+        }
+        None => convert(&args, &registry),
+    }
+}
+
+fn load_registry(args: &Args) -> Result<UnitRegistry> {
+    let mut registry = UnitRegistry::new();
+    if let Some(path) = defs::user_path().filter(|x| x.exists()) {
+        registry.load_file(&path)?;
+    }
+    for path in &args.gnu_units {
+        let report = registry.import_gnu_units_file(path)?;
+        if args.debug {
+            report.skipped.iter().for_each(|x| eprintln!("Skipped {x}"));
+        }
+    }
+    for path in &args.defs {
+        registry.load_file(path)?;
+    }
+    if let Some(atmosphere) = &args.atmosphere {
+        registry.parse_atmosphere(atmosphere)?;
+    }
+    if args.ucum {
+        registry.set_syntax(UnitSyntax::Ucum);
+    }
+
+    Ok(registry)
+}
+
+/// A unit's name and aliases, like `foot (ft, feet, ')`.
+fn list_entry(unit: &CatalogUnit) -> String {
+    match unit.aliases.is_empty() {
+        true => unit.name.to_owned(),
+        false => format!("{} ({})", unit.name, unit.aliases.join(", ")),
+    }
+}
+
+fn convert(args: &Args, registry: &UnitRegistry) -> Result<()> {
+    let input = args.input.as_deref().unwrap();
+    let inp = match args.ucum {
+        true => input::Input::parse_ucum(input)?,
+        false => input::Input::from_str(input)?,
+    };
+
+    let from = inp.from.evaluate_with(registry)?;
+    if args.dimensions && args.ucum {
+        println!("{}\n", ucum::format(&from.dimensions)?)
     } else if args.dimensions {
-        println!("{:#}\n", from_dim.simplify())
+        println!("{:#}", from.dimensions.simplify());
+        let kinds = from.dimensions.kinds();
+        if !kinds.is_empty() {
+            let kinds = kinds
+                .iter()
+                .map(|x| format!("{} ({})", x.name, x.units.join(", ")));
+            println!("Quantity: {}", kinds.collect::<Vec<_>>().join(", "));
+        }
+        println!();
     }
 
-    let val = from_dim.convert(&to_dim, inp.value, args.debug)?;
-    println!(
-        "{} {} => {} {}",
-        inp.value.separate_with_spaces(),
-        inp.from_unit,
-        val.separate_with_spaces(),
-        inp.to_unit
-    );
+    let precision = args.precision().resolve(inp.from.significant_figures());
+    let format = |x| args.notation.format_with(x, precision);
+    let from_str = inp.from.format(&format);
+
+    let results = inp.convert_all(&from, registry, args.debug);
+    let mut failed = 0;
+    for (target, result) in inp.to.iter().zip(results) {
+        match result {
+            Ok(parts) => println!("{from_str} => {}", Part::format_all(&parts, &format)),
+            Err(err) if inp.to.len() == 1 => return Err(err),
+            Err(err) => {
+                eprintln!("{from_str} => {target}: {err}");
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("Could not convert to {failed} of {} targets.", inp.to.len());
+    }
     Ok(())
 }
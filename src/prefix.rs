@@ -1,4 +1,4 @@
-use crate::units::{find_unit, ConversionType};
+use crate::{registry::UnitRegistry, units::ConversionType};
 
 #[rustfmt::skip]
 pub const METRIC_PREFIX: [Prefix; 24] = [
@@ -28,7 +28,7 @@ pub const METRIC_PREFIX: [Prefix; 24] = [
     Prefix::new("quecto", "q", -30),
 ];
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Prefix {
     pub name: &'static str,
     pub symbol: &'static str,
@@ -36,7 +36,7 @@ pub struct Prefix {
 }
 
 impl Prefix {
-    const fn new(name: &'static str, symbol: &'static str, power: i32) -> Self {
+    pub const fn new(name: &'static str, symbol: &'static str, power: i32) -> Self {
         Self {
             name,
             symbol,
@@ -45,30 +45,8 @@ impl Prefix {
     }
 }
 
-fn strip_prefix(s: &str) -> Option<(&str, &Prefix)> {
-    for prefix in METRIC_PREFIX.iter() {
-        for i in [prefix.name, prefix.symbol] {
-            if let Some(i) = s.strip_prefix(i) {
-                return Some((i, prefix));
-            }
-        }
-    }
-
-    None
-}
-
-pub fn get(s: &str) -> Option<(ConversionType, Option<&Prefix>)> {
-    if let Some(i) = find_unit(s) {
-        return Some((i, None));
-    }
-
-    let (s, prefix) = strip_prefix(s)?;
-    let unit = find_unit(s)?;
-    if !unit.is_metric() {
-        return None;
-    }
-
-    Some((unit, Some(prefix)))
+pub fn get(s: &str) -> Option<(ConversionType, Option<&'static Prefix>)> {
+    UnitRegistry::builtin().get(s)
 }
 
 #[cfg(test)]
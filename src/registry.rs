@@ -0,0 +1,525 @@
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+
+use crate::{
+    dimension::{Dimensions, Unit},
+    index::UnitIndex,
+    input::expression::ExpressionParser,
+    prefix::{Prefix, METRIC_PREFIX},
+    shorthand::{Shorthand, SHORTHANDS},
+    units::{
+        derived::{pressure, DerivedConversion, DERIVED_UNITS},
+        Conversion, ConversionType, Transform, UNIT_SPACES,
+    },
+    Num,
+};
+
+static BUILTIN: Lazy<UnitRegistry> = Lazy::new(UnitRegistry::new);
+
+/// A set of units, derived units, shorthands and prefixes to parse unit expressions with.
+/// Starts with the built in catalog, but units can be added, replaced and removed at runtime.
+///
+/// Units are looked up in the same order as [`find_unit`](crate::units::find_unit): units, then derived units, then shorthands.
+/// Names with a prefix are only split if the whole name is not a unit.
+/// Names, aliases and prefixed names are indexed, so lookups don't depend on how many units there are.
+///
+/// Added units are leaked to get the `'static` lifetime that [`Unit`](crate::dimension::Unit) needs,
+/// so registries are meant to be built once, not on every conversion.
+#[derive(Debug, Clone)]
+pub struct UnitRegistry {
+    units: Vec<&'static Conversion>,
+    derived: Vec<&'static DerivedConversion>,
+    shorthands: Vec<&'static Shorthand>,
+    prefixes: Vec<&'static Prefix>,
+    syntax: UnitSyntax,
+    index: UnitIndex,
+}
+
+/// How unit expressions parsed with a registry are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnitSyntax {
+    /// This crate's own syntax, like `kg*m/s^2`.
+    #[default]
+    Native,
+    /// [UCUM](https://ucum.org/ucum) codes, like `kg.m/s2` or `mm[Hg]`.
+    Ucum,
+}
+
+impl UnitRegistry {
+    /// Creates a registry with all the built in units, derived units, shorthands and metric prefixes.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            units: UNIT_SPACES
+                .iter()
+                .flat_map(|space| space.units.iter().copied())
+                .collect(),
+            derived: DERIVED_UNITS
+                .iter()
+                .flat_map(|units| units.iter().copied())
+                .collect(),
+            shorthands: SHORTHANDS.iter().collect(),
+            prefixes: METRIC_PREFIX.iter().collect(),
+            syntax: UnitSyntax::Native,
+            index: UnitIndex::default(),
+        };
+        registry.reindex();
+        registry
+    }
+
+    /// Creates a registry without any units or prefixes.
+    pub fn empty() -> Self {
+        Self {
+            units: Vec::new(),
+            derived: Vec::new(),
+            shorthands: Vec::new(),
+            prefixes: Vec::new(),
+            syntax: UnitSyntax::Native,
+            index: UnitIndex::default(),
+        }
+    }
+
+    /// The shared registry of built in units.
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    pub fn units(&self) -> &[&'static Conversion] {
+        &self.units
+    }
+
+    pub fn derived_units(&self) -> &[&'static DerivedConversion] {
+        &self.derived
+    }
+
+    pub fn shorthands(&self) -> &[&'static Shorthand] {
+        &self.shorthands
+    }
+
+    pub fn prefixes(&self) -> &[&'static Prefix] {
+        &self.prefixes
+    }
+
+    pub fn syntax(&self) -> UnitSyntax {
+        self.syntax
+    }
+
+    /// Sets how unit expressions are read, see [`Dimensions::parse_with`](crate::dimension::Dimensions::parse_with).
+    pub fn set_syntax(&mut self, syntax: UnitSyntax) {
+        self.syntax = syntax;
+    }
+
+    /// Finds a unit, derived unit or shorthand by name or alias.
+    pub fn find_unit(&self, s: &str) -> Option<ConversionType> {
+        self.index.find(s)
+    }
+
+    /// Finds a unit, allowing a prefix if the unit is metric.
+    /// Works like [`prefix::get`](crate::prefix::get), but with the units and prefixes of this registry.
+    /// If several prefixes match, the first one leaving a metric unit is used.
+    pub fn get(&self, s: &str) -> Option<(ConversionType, Option<&'static Prefix>)> {
+        if let Some(i) = self.find_unit(s) {
+            return Some((i, None));
+        }
+
+        self.index.splits(s).find_map(|(prefix, s)| {
+            let unit = self.find_unit(s).filter(|unit| unit.is_metric())?;
+            Some((unit, Some(prefix)))
+        })
+    }
+
+    /// Adds a unit, failing if its name or any alias is already taken.
+    pub fn add_unit(&mut self, unit: Conversion) -> Result<&'static Conversion> {
+        self.check_names(unit.name, unit.aliases)?;
+        let unit = &*Box::leak(Box::new(unit));
+        self.units.push(unit);
+        self.index.add(
+            ConversionType::Conversion(unit),
+            self.units.len() - 1,
+            &self.prefixes,
+        );
+        Ok(unit)
+    }
+
+    /// Adds a derived unit, failing if its name or any alias is already taken.
+    pub fn add_derived(&mut self, unit: DerivedConversion) -> Result<&'static DerivedConversion> {
+        self.check_names(unit.name, unit.aliases)?;
+        let unit = &*Box::leak(Box::new(unit));
+        self.derived.push(unit);
+        self.index.add(
+            ConversionType::DerivedConversion(unit),
+            self.derived.len() - 1,
+            &self.prefixes,
+        );
+        Ok(unit)
+    }
+
+    /// Adds a shorthand, failing if its name is already taken.
+    pub fn add_shorthand(&mut self, shorthand: Shorthand) -> Result<&'static Shorthand> {
+        self.check_names(shorthand.name, &[])?;
+        let shorthand = &*Box::leak(Box::new(shorthand));
+        self.shorthands.push(shorthand);
+        self.index.add(
+            ConversionType::Shorthand(shorthand),
+            self.shorthands.len() - 1,
+            &self.prefixes,
+        );
+        Ok(shorthand)
+    }
+
+    /// Adds a prefix, failing if its name or symbol is already used by another prefix.
+    pub fn add_prefix(&mut self, prefix: Prefix) -> Result<&'static Prefix> {
+        if let Some(existing) = self.prefixes.iter().find(|x| {
+            [x.name, x.symbol]
+                .iter()
+                .any(|x| *x == prefix.name || *x == prefix.symbol)
+        }) {
+            bail!(
+                "Prefix `{}` conflicts with the existing prefix `{}`",
+                prefix.name,
+                existing.name
+            );
+        }
+
+        let prefix = &*Box::leak(Box::new(prefix));
+        self.prefixes.push(prefix);
+        self.index.add_prefix(prefix, self.prefixes.len() - 1);
+        Ok(prefix)
+    }
+
+    /// Adds a unit, first removing anything using its name or aliases.
+    pub fn replace_unit(&mut self, unit: Conversion) -> &'static Conversion {
+        self.remove_names(unit.name, unit.aliases);
+        self.add_unit(unit).expect("Conflicting names were removed")
+    }
+
+    /// Adds a derived unit, first removing anything using its name or aliases.
+    pub fn replace_derived(&mut self, unit: DerivedConversion) -> &'static DerivedConversion {
+        self.remove_names(unit.name, unit.aliases);
+        self.add_derived(unit)
+            .expect("Conflicting names were removed")
+    }
+
+    /// Adds a shorthand, first removing anything using its name.
+    pub fn replace_shorthand(&mut self, shorthand: Shorthand) -> &'static Shorthand {
+        self.remove_names(shorthand.name, &[]);
+        self.add_shorthand(shorthand)
+            .expect("Conflicting names were removed")
+    }
+
+    /// Adds a prefix, first removing any prefix using its name or symbol.
+    pub fn replace_prefix(&mut self, prefix: Prefix) -> &'static Prefix {
+        self.remove_prefix(prefix.name);
+        self.remove_prefix(prefix.symbol);
+        self.add_prefix(prefix)
+            .expect("Conflicting prefixes were removed")
+    }
+
+    /// Removes the unit, derived unit or shorthand found by this name or alias.
+    /// Returns `false` if nothing was found.
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.find_unit(name) {
+            Some(ConversionType::Conversion(unit)) => {
+                self.units.retain(|x| !std::ptr::eq(*x, unit))
+            }
+            Some(ConversionType::DerivedConversion(unit)) => {
+                self.derived.retain(|x| !std::ptr::eq(*x, unit))
+            }
+            Some(ConversionType::Shorthand(unit)) => {
+                self.shorthands.retain(|x| !std::ptr::eq(*x, unit))
+            }
+            None => return false,
+        }
+
+        self.reindex();
+        true
+    }
+
+    /// Removes the prefix with this name or symbol.
+    /// Returns `false` if no prefix was found.
+    pub fn remove_prefix(&mut self, name: &str) -> bool {
+        let len = self.prefixes.len();
+        self.prefixes.retain(|x| x.name != name && x.symbol != name);
+        if self.prefixes.len() == len {
+            return false;
+        }
+
+        self.reindex();
+        true
+    }
+
+    /// Sets the atmosphere from a pressure expression, like `83.4 kPa`.
+    /// Gauge pressures are rejected, as they would be relative to the atmosphere being set.
+    pub fn parse_atmosphere(&mut self, pressure: &str) -> Result<()> {
+        let quantity = ExpressionParser::parse(pressure)?.evaluate_with(self)?;
+        let pascal = Dimensions::parse_native("Pa", self)?;
+        if quantity.dimensions != pascal {
+            bail!("The atmosphere `{pressure}` is not a pressure");
+        }
+        if quantity.dimensions.has_offset() {
+            bail!(
+                "The atmosphere `{pressure}` has to be an absolute pressure, not a gauge pressure"
+            );
+        }
+
+        let pascals = quantity
+            .dimensions
+            .convert(&pascal, quantity.value, false)?;
+        self.set_atmosphere(pascals);
+        Ok(())
+    }
+
+    /// Sets the atmospheric pressure that gauge units, like `psig`, are relative to, in pascals.
+    /// Starts as the [standard atmosphere](crate::units::derived::pressure::STANDARD_ATMOSPHERE).
+    pub fn set_atmosphere(&mut self, pascals: Num) {
+        let gauge: &'static Conversion = Box::leak(Box::new(Conversion {
+            transform: Transform::affine(1.0, pascals * 1e3),
+            ..pressure::GAUGE
+        }));
+
+        for unit in self.derived.iter_mut() {
+            if !unit.expand.iter().any(|x| x.is_special() && x.has_offset()) {
+                continue;
+            }
+
+            let expand = unit
+                .expand
+                .iter()
+                .map(|x| match x.is_special() && x.has_offset() {
+                    true => Unit::new(gauge, x.power(), x.sci_exponent()),
+                    false => *x,
+                })
+                .collect::<Vec<_>>();
+            *unit = Box::leak(Box::new(DerivedConversion {
+                expand: Box::leak(expand.into_boxed_slice()),
+                ..(*unit).clone()
+            }));
+        }
+        self.reindex();
+    }
+
+    /// Rebuilds the name index after units or prefixes were removed or changed.
+    fn reindex(&mut self) {
+        let mut index = UnitIndex::default();
+        let units = (self
+            .units
+            .iter()
+            .map(|&x| ConversionType::Conversion(x))
+            .enumerate())
+        .chain(
+            (self
+                .derived
+                .iter()
+                .map(|&x| ConversionType::DerivedConversion(x)))
+            .enumerate(),
+        )
+        .chain(
+            (self
+                .shorthands
+                .iter()
+                .map(|&x| ConversionType::Shorthand(x)))
+            .enumerate(),
+        );
+        for (position, unit) in units {
+            index.add(unit, position, &self.prefixes);
+        }
+        self.index = index;
+    }
+
+    fn check_names(&self, name: &str, aliases: &[&str]) -> Result<()> {
+        for name in [name].iter().chain(aliases) {
+            if self.find_unit(name).is_some() {
+                bail!("A unit named `{name}` already exists");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_names(&mut self, name: &str, aliases: &[&str]) {
+        for name in [name].iter().chain(aliases) {
+            while self.remove(name) {}
+        }
+    }
+}
+
+impl Default for UnitRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use approx::assert_relative_eq;
+
+    use super::UnitRegistry;
+    use crate::{
+        dimension::Dimensions,
+        prefix::Prefix,
+        units::{Conversion, ConversionType, Space, Transform},
+    };
+
+    /// Finds a unit by going through every unit and prefix in order, like before the index.
+    fn find_in_order(registry: &UnitRegistry, s: &str) -> Option<(String, Option<&'static str>)> {
+        let find = |s: &str| {
+            let lower = s.to_ascii_lowercase();
+            (registry.units.iter())
+                .find(|u| u.is_alias(s))
+                .map(|&u| ConversionType::Conversion(u))
+                .or_else(|| {
+                    (registry.derived.iter())
+                        .find(|u| u.name == lower || u.aliases.contains(&s))
+                        .map(|&u| ConversionType::DerivedConversion(u))
+                })
+                .or_else(|| {
+                    (registry.shorthands.iter())
+                        .find(|u| u.name == s)
+                        .map(|&u| ConversionType::Shorthand(u))
+                })
+        };
+
+        let found = find(s).map(|x| (x, None)).or_else(|| {
+            registry.prefixes.iter().find_map(|&prefix| {
+                [prefix.name, prefix.symbol]
+                    .iter()
+                    .filter_map(|x| s.strip_prefix(x))
+                    .filter_map(find)
+                    .find(|unit| unit.is_metric())
+                    .map(|unit| (unit, Some(prefix)))
+            })
+        });
+        found.map(|(unit, prefix)| (name(&unit), prefix.map(|x| x.name)))
+    }
+
+    /// Every name, alias and shorthand in the registry.
+    fn names_of(registry: &UnitRegistry) -> Vec<&'static str> {
+        let units = (registry.units.iter())
+            .flat_map(|x| [x.name].into_iter().chain(x.aliases.iter().copied()));
+        let derived = (registry.derived.iter())
+            .flat_map(|x| [x.name].into_iter().chain(x.aliases.iter().copied()));
+        (units.chain(derived))
+            .chain(registry.shorthands.iter().map(|x| x.name))
+            .collect()
+    }
+
+    fn name(unit: &ConversionType) -> String {
+        match unit {
+            ConversionType::Conversion(x) => format!("unit {}", x.name),
+            ConversionType::DerivedConversion(x) => format!("derived {}", x.name),
+            ConversionType::Shorthand(x) => format!("shorthand {}", x.name),
+        }
+    }
+
+    #[test]
+    fn test_index_order() {
+        let mut registry = UnitRegistry::new();
+        registry
+            .add_unit(Conversion {
+                aliases: &["wsk"],
+                metric: true,
+                ..Conversion::new("whisker", Space::Length, Transform::scale(0.1))
+            })
+            .unwrap();
+
+        let mut names = Vec::new();
+        for unit in names_of(&registry) {
+            names.push(unit.to_owned());
+            names.push(unit.to_uppercase());
+            for prefix in &registry.prefixes {
+                names.push(format!("{}{unit}", prefix.name));
+                names.push(format!("{}{unit}", prefix.symbol));
+            }
+        }
+        names
+            .extend(["min", "cd", "Pa", "mm", "kMeter", "KM", "dam", "notaunit"].map(String::from));
+
+        for s in names {
+            let found = registry
+                .get(&s)
+                .map(|(unit, prefix)| (name(&unit), prefix.map(|x| x.name)));
+            assert_eq!(found, find_in_order(&registry, &s), "looking up `{s}`");
+        }
+    }
+
+    #[test]
+    fn test_add_unit() {
+        let mut registry = UnitRegistry::new();
+        registry
+            .add_unit(Conversion::new(
+                "pallet",
+                Space::Mass,
+                Transform::scale(40_000.0),
+            ))
+            .unwrap();
+
+        let pallet = Dimensions::parse_with("pallet", &registry).unwrap();
+        let kg = Dimensions::from_str("kg").unwrap();
+        assert_eq!(pallet.convert(&kg, 2.0, false).unwrap(), 80.0);
+
+        assert!(Dimensions::from_str("pallet").is_err());
+        assert!(registry
+            .add_unit(Conversion::new(
+                "meter",
+                Space::Length,
+                Transform::scale(1.0)
+            ))
+            .is_err());
+    }
+
+    #[test]
+    fn test_replace_and_remove() {
+        let mut registry = UnitRegistry::new();
+        registry.replace_unit(Conversion {
+            aliases: &["ft"],
+            ..Conversion::new("foot", Space::Length, Transform::scale(0.3))
+        });
+
+        let ft = Dimensions::parse_with("ft", &registry).unwrap();
+        let m = Dimensions::from_str("m").unwrap();
+        assert_eq!(ft.convert(&m, 10.0, false).unwrap(), 3.0);
+
+        assert!(registry.remove("ft"));
+        assert!(!registry.remove("ft"));
+        assert!(Dimensions::parse_with("ft", &registry).is_err());
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let mut registry = UnitRegistry::new();
+        assert!(registry.add_prefix(Prefix::new("kibi", "k", 3)).is_err());
+
+        registry.remove_prefix("kilo");
+        assert!(Dimensions::parse_with("km", &registry).is_err());
+
+        registry.add_prefix(Prefix::new("myria", "my", 4)).unwrap();
+        let my = Dimensions::parse_with("mym", &registry).unwrap();
+        let m = Dimensions::from_str("m").unwrap();
+        assert_eq!(my.convert(&m, 1.0, false).unwrap(), 10_000.0);
+    }
+
+    #[test]
+    fn test_atmosphere() {
+        let mut registry = UnitRegistry::new();
+        registry.set_atmosphere(83_400.0);
+
+        let psig = Dimensions::parse_with("psig", &registry).unwrap();
+        let kpa = Dimensions::from_str("kPa").unwrap();
+        assert_relative_eq!(psig.convert(&kpa, 0.0, false).unwrap(), 83.4);
+
+        let builtin = Dimensions::from_str("psig").unwrap();
+        assert_relative_eq!(builtin.convert(&kpa, 0.0, false).unwrap(), 101.325);
+
+        registry.parse_atmosphere("12 psi").unwrap();
+        let psig = Dimensions::parse_with("psig", &registry).unwrap();
+        assert_relative_eq!(
+            psig.convert(&kpa, 0.0, false).unwrap(),
+            82.74,
+            epsilon = 0.01
+        );
+
+        assert!(registry.parse_atmosphere("12 psig").is_err());
+        assert!(registry.parse_atmosphere("12 m").is_err());
+    }
+}
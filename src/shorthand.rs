@@ -53,7 +53,7 @@ pub fn get(s: &str) -> Option<&'static Shorthand> {
 }
 
 impl Shorthand {
-    const fn new(name: &'static str, unit: &'static [HandUnit]) -> Self {
+    pub const fn new(name: &'static str, unit: &'static [HandUnit]) -> Self {
         Self {
             name,
             unit,
@@ -61,7 +61,7 @@ impl Shorthand {
         }
     }
 
-    const fn new_metric(name: &'static str, unit: &'static [HandUnit]) -> Self {
+    pub const fn new_metric(name: &'static str, unit: &'static [HandUnit]) -> Self {
         Self {
             name,
             unit,
@@ -71,7 +71,7 @@ impl Shorthand {
 }
 
 impl HandUnit {
-    const fn new(conversion: &'static Conversion, power: f64, sci_exponent: f64) -> Self {
+    pub const fn new(conversion: &'static Conversion, power: f64, sci_exponent: f64) -> Self {
         Self {
             conversion: ConversionType::Conversion(conversion),
             power,
@@ -79,7 +79,7 @@ impl HandUnit {
         }
     }
 
-    const fn new_derived(
+    pub const fn new_derived(
         conversion: &'static DerivedConversion,
         power: f64,
         sci_exponent: f64,
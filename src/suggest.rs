@@ -0,0 +1,161 @@
+//! Suggestions for misspelled unit names.
+
+use crate::{registry::UnitRegistry, units::ConversionType};
+
+/// The most suggestions [`suggest`] returns.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Finds the unit names closest to `name`, best first.
+/// Names are compared ignoring case, a plural `s` and separators, then by edit distance.
+/// Metric units are also matched with any prefix, so `kilometre` suggests `kilometer`.
+pub fn suggest(name: &str, registry: &UnitRegistry) -> Vec<String> {
+    // Sorted by distance, then unprefixed names before prefixed ones.
+    let mut found = Vec::<((usize, bool), String)>::new();
+    let mut add = |rank: (usize, bool), name: String| {
+        let name = name.replace(' ', "_");
+        match found.iter_mut().find(|x| x.1 == name) {
+            Some(x) => x.0 = x.0.min(rank),
+            None => found.push((rank, name)),
+        }
+    };
+
+    let names = |unit: ConversionType| -> Vec<&'static str> {
+        match unit {
+            ConversionType::Conversion(x) => [x.name].iter().chain(x.aliases).copied().collect(),
+            ConversionType::DerivedConversion(x) => {
+                [x.name].iter().chain(x.aliases).copied().collect()
+            }
+            ConversionType::Shorthand(x) => vec![x.name],
+        }
+    };
+    let units = (registry.units().iter())
+        .map(|&x| ConversionType::Conversion(x))
+        .chain((registry.derived_units().iter()).map(|&x| ConversionType::DerivedConversion(x)))
+        .chain((registry.shorthands().iter()).map(|&x| ConversionType::Shorthand(x)));
+
+    let lower = name.to_lowercase();
+    for unit in units {
+        let metric = unit.is_metric();
+        for candidate in names(unit) {
+            if let Some(distance) = close(name, candidate) {
+                add((distance, false), candidate.to_owned());
+            }
+
+            if !metric {
+                continue;
+            }
+            for prefix in registry.prefixes() {
+                for symbol in [prefix.name, prefix.symbol] {
+                    let rest = lower.strip_prefix(&symbol.to_lowercase());
+                    let Some(rest) = rest.filter(|x| !x.is_empty()) else {
+                        continue;
+                    };
+                    if let Some(distance) = close(rest, candidate) {
+                        add((distance, true), format!("{symbol}{candidate}"));
+                    }
+                }
+            }
+        }
+    }
+
+    found.sort_by_key(|x| x.0);
+    found
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|x| x.1)
+        .collect()
+}
+
+/// A hint to add to an error about an unknown unit, like `. Did you mean `meter` or `metre`?`.
+/// Empty if nothing is close.
+pub fn hint(name: &str, registry: &UnitRegistry) -> String {
+    let suggestions = suggest(name, registry)
+        .into_iter()
+        .map(|x| format!("`{x}`"))
+        .collect::<Vec<_>>();
+
+    match suggestions.as_slice() {
+        [] => String::new(),
+        [one] => format!(". Did you mean {one}?"),
+        [rest @ .., last] => format!(". Did you mean {} or {last}?", rest.join(", ")),
+    }
+}
+
+/// The edit distance between two names, if they are close enough to be a typo.
+fn close(name: &str, candidate: &str) -> Option<usize> {
+    let (name, candidate) = (normalize(name), normalize(candidate));
+    if name.is_empty() || candidate.is_empty() {
+        return None;
+    }
+
+    let distance = edit_distance(&name, &candidate);
+    (distance <= (name.chars().count() / 3).max(1)).then_some(distance)
+}
+
+/// Lowercases a name and removes separators and a plural `s`.
+fn normalize(name: &str) -> String {
+    let mut name = name
+        .chars()
+        .filter(|x| !matches!(x, ' ' | '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect::<String>();
+
+    if name.chars().count() > 3 && name.ends_with('s') {
+        name.pop();
+    }
+    name
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut last = row[0];
+        row[0] = i + 1;
+        for (j, &b) in b.iter().enumerate() {
+            let next = (last + (a != b) as usize)
+                .min(row[j] + 1)
+                .min(row[j + 1] + 1);
+            last = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{edit_distance, hint, suggest};
+    use crate::registry::UnitRegistry;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("meter", "meter"), 0);
+        assert_eq!(edit_distance("metr", "meter"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let registry = UnitRegistry::builtin();
+        let suggest = |x| suggest(x, registry);
+
+        assert_eq!(suggest("metr")[0], "meter");
+        assert_eq!(suggest("Meters")[0], "meter");
+        assert_eq!(suggest("KM")[0], "km");
+        assert_eq!(suggest("kilometr")[0], "kilometer");
+        assert_eq!(suggest("statute_miles")[0], "statutemile");
+        assert!(suggest("qwertyuiop").is_empty());
+    }
+
+    #[test]
+    fn test_hint() {
+        let registry = UnitRegistry::builtin();
+        assert!(hint("fortnigt", registry).starts_with(". Did you mean `fortnight`"));
+        assert_eq!(hint("qwertyuiop", registry), "");
+    }
+}
@@ -5,7 +5,6 @@ use approx::assert_abs_diff_eq;
 use hashbrown::HashSet;
 
 use crate::{
-    dimension::Dimensions,
     input,
     units::{derived::DERIVED_UNITS, UNIT_SPACES},
     Num,
@@ -14,13 +13,10 @@ use crate::{
 const ERROR: Num = 0.01;
 
 fn convert(inp: &str) -> Result<Num> {
-    let inp = input::Input::from_str(&inp)?;
+    let inp = input::Input::from_str(inp)?;
 
-    let from_dim = Dimensions::from_str(&inp.from_unit)?;
-    let to_dim = Dimensions::from_str(&inp.to_unit)?;
-
-    let val = from_dim.convert(&to_dim, inp.value, true)?;
-    Ok(val)
+    let parts = inp.convert(true)?.remove(0)?;
+    Ok(parts.last().unwrap().value)
 }
 
 macro_rules! tests {
@@ -46,7 +42,12 @@ tests! {
         "10m/s => cm/s" => 1_000.0,
         "10m/s^2 => mi/h^2" => 80_529.71,
         "10 m/s^3 => yard/s^3" => 10.94,
-        "10 yard/ms^2 => feet/s^2" => 30_000.0
+        "10 yard/ms^2 => feet/s^2" => 30_000_000.0
+    ],
+    mass => [
+        "1 t => kg" => 1_000.0,
+        "1000000 g => t" => 1.0,
+        "2.5 t => lb" => 5_511.56
     ],
     derived => [
         "1E2 footcandle to hefnerkerze*rad/ft^2" => 110.74,
@@ -59,10 +60,115 @@ tests! {
     prefix => [
         "50 kWh => J" => 180_000_000.0,
         "30 kHz => Hz" => 30_000.0,
-        "30 kHz => GHz" => 0.00003
+        "30 kHz => GHz" => 0.00003,
+        "1 km^2 => m^2" => 1_000_000.0,
+        "1500 marcsec => arcsec" => 1.5,
+        "1 arcsec => μarcsec" => 1_000_000.0
+    ],
+    fractional_power => [
+        "1 m^(3/2) => cm^(3/2)" => 1_000.0,
+        "1 V/Hz^0.5 => mV/Hz^0.5" => 1_000.0,
+        "5 nV/Hz^0.5 => nV*s^(1/2)" => 5.0,
+        "1 ft^(1/2) => in^0.5" => 3.46
+    ],
+    number_literal => [
+        "1,000 m => km" => 1.0,
+        "1_000 eV => keV" => 1.0,
+        "3/4 ft => in" => 9.0,
+        "1 1/2 ft => in" => 18.0,
+        "0x10 m => m" => 16.0,
+        "2.5e-3 km => m" => 2.5,
+        "0bar => psi" => 0.0
+    ],
+    arithmetic => [
+        "3 ft + 4 in => cm" => 101.6,
+        "1 h - 15 min => min" => 45.0,
+        "(10 km / 2 h) => m/s" => 1.39,
+        "2 m * 3 m => ft^2" => 64.58,
+        "1 mi / 2 => km" => 0.80
+    ],
+    compound => [
+        "5 ft 3 in => in" => 63.0,
+        "1h20m5s => s" => 4805.0,
+        "-1 h 30 min => min" => -90.0,
+        "12° 30' 36\" => deg" => 12.51,
+        "1 D 2 h => h" => 26.0
+    ],
+    root_function => [
+        "1 V/sqrt(Hz) => V/Hz^0.5" => 1.0,
+        "60 m/s/sqrt(h) => m/s/sqrt(s)" => 1.0,
+        "1 cbrt(km^3) => m" => 1_000.0
+    ],
+    temperature => [
+        "20 degC => degF" => 68.0,
+        "300 K => degC" => 26.85,
+        "10 degC/min => K/s" => 0.1667,
+        "1 J/degF => J/K" => 1.8,
+        "4184 J/(kg*degC) => J/(kg*K)" => 4184.0,
+        "10 ΔdegC => delta_degF" => 18.0,
+        "18 delta_degF => K" => 10.0,
+        "10 delta_celsius => delta_f" => 18.0
+    ],
+    gauge_pressure => [
+        "0 psig => psia" => 14.696,
+        "30 psig => kPa" => 308.17,
+        "1 atm => barg" => 0.0,
+        "2 barg => psig" => 29.01,
+        "100 kPag => kPa" => 201.325,
+        "10 psig/s => kPa/s" => 68.95
     ]
 }
 
+#[test]
+fn test_multiple_targets() {
+    let inp = input::Input::from_str("1 mi => km, m, s, ft + in").unwrap();
+    let results = inp.convert(false).unwrap();
+    assert_eq!(results.len(), 4);
+
+    assert_abs_diff_eq!(results[0].as_ref().unwrap()[0].value, 1.61, epsilon = ERROR);
+    assert_abs_diff_eq!(
+        results[1].as_ref().unwrap()[0].value,
+        1609.34,
+        epsilon = ERROR
+    );
+    assert!(results[2].is_err());
+    assert_eq!(results[3].as_ref().unwrap()[0].value, 5280.0);
+}
+
+#[test]
+fn test_prefixed_arcminutes() {
+    assert!(convert("1 marcmin => arcsec").is_err());
+}
+
+#[test]
+fn test_exact_temperatures() {
+    assert_eq!(convert("20 degC => degF").unwrap(), 68.0);
+    assert_eq!(convert("1 degC => °F").unwrap(), 33.8);
+    assert_eq!(convert("68 degF => degC").unwrap(), 20.0);
+    assert_eq!(convert("-40 degF => degC").unwrap(), -40.0);
+}
+
+#[test]
+fn test_units_are_linear() {
+    for space in UNIT_SPACES {
+        for unit in space.units {
+            let linear = unit.transform.linear();
+            assert!(
+                linear.is_some_and(|(scale, _)| scale > 0.0),
+                "{} is not a positive scale of its base unit",
+                unit.name
+            );
+
+            let inverse = unit.transform.inverse();
+            assert_abs_diff_eq!(
+                inverse.to_base(unit.transform.to_base(3.0)),
+                3.0,
+                epsilon = 1e-9
+            );
+        }
+    }
+}
+
 #[test]
 fn test_name_collisions() {
     let mut sack = HashSet::new();
@@ -0,0 +1,516 @@
+//! Reading and writing [UCUM](https://ucum.org/ucum) unit codes, like `mg/dL`, `mm[Hg]` or `10*3/uL`.
+//!
+//! UCUM atoms are mapped onto the units of this crate, so only atoms with an equivalent here are supported.
+//! Codes are case sensitive, `.` multiplies, `/` divides and a number after an atom is its power (`s-2`).
+//! Annotations in curly braces (`{cells}`) are ignored.
+
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+
+use crate::{
+    dimension::{Dimensions, Unit, POWER_EPSILON},
+    registry::UnitRegistry,
+    units::{Conversion, Space},
+    Num,
+};
+
+/// UCUM atoms, with the units of this crate they are made of and if they can take a prefix.
+/// The first atom for a unit is used when formatting.
+#[rustfmt::skip]
+const ATOMS: &[(&str, &str, Num, bool)] = &[
+    // Base units
+    ("m",        "m",           1.0,                 true),
+    ("g",        "g",           1.0,                 true),
+    ("s",        "s",           1.0,                 true),
+    ("A",        "A",           1.0,                 true),
+    ("K",        "K",           1.0,                 true),
+    ("mol",      "mol",         1.0,                 true),
+    ("cd",       "cd",          1.0,                 true),
+    ("rad",      "rad",         1.0,                 true),
+    // Dimensionless
+    ("10*",      "",            10.0,                false),
+    ("10^",      "",            10.0,                false),
+    ("%",        "",            0.01,                false),
+    ("[ppth]",   "",            1e-3,                false),
+    ("[ppm]",    "",            1e-6,                false),
+    ("[pi]",     "",            std::f64::consts::PI, false),
+    // SI derived units
+    ("sr",       "sr",          1.0,                 true),
+    ("Hz",       "Hz",          1.0,                 true),
+    ("N",        "N",           1.0,                 true),
+    ("Pa",       "Pa",          1.0,                 true),
+    ("J",        "J",           1.0,                 true),
+    ("W",        "W",           1.0,                 true),
+    ("C",        "C",           1.0,                 true),
+    ("V",        "V",           1.0,                 true),
+    ("F",        "F",           1.0,                 true),
+    ("Ohm",      "ohm",         1.0,                 true),
+    ("S",        "S",           1.0,                 true),
+    ("Wb",       "Wb",          1.0,                 true),
+    ("T",        "T",           1.0,                 true),
+    ("H",        "H",           1.0,                 true),
+    ("lm",       "lm",          1.0,                 true),
+    ("lx",       "lx",          1.0,                 true),
+    ("Bq",       "Bq",          1.0,                 true),
+    ("Gy",       "Gy",          1.0,                 true),
+    ("Sv",       "Sv",          1.0,                 true),
+    ("kat",      "kat",         1.0,                 true),
+    ("Cel",      "degc",        1.0,                 true),
+    // Other metric units
+    ("L",        "dm^3",        1.0,                 true),
+    ("l",        "dm^3",        1.0,                 true),
+    ("t",        "t",           1.0,                 true),
+    ("bar",      "bar",         1.0,                 true),
+    ("eV",       "eV",          1.0,                 true),
+    ("u",        "g",           1.660_539_066_6e-24, true),
+    ("pc",       "pc",          1.0,                 true),
+    ("erg",      "erg",         1.0,                 true),
+    ("dyn",      "dyn",         1.0,                 true),
+    ("Mx",       "Mx",          1.0,                 true),
+    ("G",        "T",           1e-4,                true),
+    ("Ci",       "Ci",          1.0,                 true),
+    ("RAD",      "Gy",          0.01,                true),
+    ("REM",      "Sv",          0.01,                true),
+    ("cal",      "cal",         1.0,                 true),
+    ("m[Hg]",    "Pa",          133_322.387_415,     true),
+    ("m[H2O]",   "Pa",          9_806.65,            true),
+    ("[ly]",     "ly",          1.0,                 true),
+    // Time and angles
+    ("min",      "min",         1.0,                 false),
+    ("h",        "h",           1.0,                 false),
+    ("d",        "d",           1.0,                 false),
+    ("wk",       "wk",          1.0,                 false),
+    ("a",        "julian_year", 1.0,                 false),
+    ("a_j",      "julian_year", 1.0,                 false),
+    ("mo",       "julian_year", 1.0 / 12.0,          false),
+    ("mo_j",     "julian_year", 1.0 / 12.0,          false),
+    ("deg",      "deg",         1.0,                 false),
+    ("'",        "arcmin",      1.0,                 false),
+    ("''",       "arcsec",      1.0,                 false),
+    ("gon",      "gon",         1.0,                 false),
+    ("circ",     "turn",        1.0,                 false),
+    ("AU",       "au",          1.0,                 false),
+    ("Ao",       "m",           1e-10,               false),
+    ("atm",      "atm",         1.0,                 false),
+    // Customary units
+    ("[in_i]",   "in",          1.0,                 false),
+    ("[ft_i]",   "ft",          1.0,                 false),
+    ("[yd_i]",   "yd",          1.0,                 false),
+    ("[mi_i]",   "mi",          1.0,                 false),
+    ("[nmi_i]",  "nmi",         1.0,                 false),
+    ("[mil_i]",  "mil",         1.0,                 false),
+    ("[hd_i]",   "hh",          1.0,                 false),
+    ("[fur_us]", "fur",         1.0,                 false),
+    ("[lb_av]",  "lb",          1.0,                 false),
+    ("[oz_av]",  "lb",          1.0 / 16.0,          false),
+    ("[stone_av]", "lb",        14.0,                false),
+    ("[gr]",     "g",           0.064_798_91,        false),
+    ("[gal_us]", "m^3",         3.785_411_784e-3,    false),
+    ("[qt_us]",  "m^3",         9.463_529_46e-4,     false),
+    ("[pt_us]",  "m^3",         4.731_764_73e-4,     false),
+    ("[cup_us]", "m^3",         2.365_882_365e-4,    false),
+    ("[foz_us]", "m^3",         2.957_352_956_25e-5, false),
+    ("[tbs_us]", "m^3",         1.478_676_478_125e-5, false),
+    ("[tsp_us]", "m^3",         4.928_921_593_75e-6, false),
+    ("[gal_br]", "m^3",         4.546_09e-3,         false),
+    ("[lbf_av]", "lbf",         1.0,                 false),
+    ("[psi]",    "psi",         1.0,                 false),
+    ("[in_i'Hg]", "Pa",         3_386.388_157_89,    false),
+    ("[Btu]",    "btu",         1.0,                 false),
+    ("[Cal]",    "cal",         1000.0,              false),
+    ("[HP]",     "W",           745.699_871_582,     false),
+    ("[degF]",   "degf",        1.0,                 false),
+    ("[degR]",   "degr",        1.0,                 false),
+];
+
+/// UCUM prefixes and their power of ten, with `da` first so it isn't read as `d`.
+#[rustfmt::skip]
+const PREFIXES: &[(&str, i32)] = &[
+    ("da", 1),
+    ("Q", 30), ("R", 27), ("Y", 24), ("Z", 21), ("E", 18), ("P", 15), ("T", 12), ("G", 9), ("M", 6), ("k", 3), ("h", 2),
+    ("d", -1), ("c", -2), ("m", -3), ("u", -6), ("n", -9), ("p", -12), ("f", -15), ("a", -18), ("z", -21), ("y", -24), ("r", -27), ("q", -30),
+];
+
+/// The atom used for units of each space that don't have an atom of their own.
+const BASE_ATOMS: &[(Space, &str)] = &[
+    (Space::Angle, "rad"),
+    (Space::ElectricCurrent, "A"),
+    (Space::Length, "m"),
+    (Space::LuminousIntensity, "cd"),
+    (Space::Mass, "g"),
+    (Space::Quantity, "mol"),
+    (Space::Temperature, "K"),
+    (Space::Time, "s"),
+];
+
+/// Atoms that are exactly one built in unit, used to format units back into atoms.
+static UNIT_ATOMS: Lazy<Vec<(&'static str, &'static Conversion, bool)>> = Lazy::new(|| {
+    let registry = UnitRegistry::builtin();
+    ATOMS
+        .iter()
+        .filter(|(_, _, factor, _)| *factor == 1.0)
+        .filter_map(|&(code, unit, _, metric)| {
+            let dimensions = Dimensions::parse_native(unit, registry).ok()?;
+            match dimensions.units() {
+                [unit] if unit.power() == 1.0 && unit.sci_exponent() == 0.0 => {
+                    Some((code, unit.conversion(), metric))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+});
+
+/// Parses a UCUM code, using the units in `registry` for each atom.
+pub fn parse(code: &str, registry: &UnitRegistry) -> Result<Dimensions> {
+    let mut parser = Parser {
+        chars: code.trim().chars().collect(),
+        index: 0,
+        registry,
+    };
+
+    let dimensions = parser.term()?;
+    if let Some(chr) = parser.peek() {
+        bail!("Unexpected `{chr}` in UCUM code `{code}`");
+    }
+
+    Ok(dimensions)
+}
+
+/// Writes dimensions as a UCUM code.
+/// Units without a UCUM atom are written in the base unit of their space, with a factor like `17.10*-1.m`.
+/// Fails if any unit has a non integer power, which UCUM can't represent.
+pub fn format(dimensions: &Dimensions) -> Result<String> {
+    let mut factor = 1.0;
+    // The code, power, if it can take a prefix and power of ten of each atom.
+    let mut atoms = Vec::<(String, Num, bool, Num)>::new();
+    let mut add_atom =
+        |code: &str, power, metric, exponent| match atoms.iter_mut().find(|x| x.0 == code) {
+            Some(atom) => {
+                atom.1 += power;
+                atom.3 += exponent;
+            }
+            None => atoms.push((code.to_owned(), power, metric, exponent)),
+        };
+
+    for unit in dimensions.units() {
+        let power = unit.power();
+        if unit.is_special() && unit.has_offset() {
+            bail!("UCUM can not represent gauge pressures");
+        } else if unit.is_special() {
+            factor *= unit.to_base(1.0);
+        } else if let Some(&(code, _, metric)) = UNIT_ATOMS
+            .iter()
+            .find(|x| std::ptr::eq(x.1, unit.conversion()))
+        {
+            add_atom(code, power, metric, unit.sci_exponent() * power);
+        } else {
+            let space = unit.conversion().space;
+            let code = BASE_ATOMS
+                .iter()
+                .find(|x| x.0 == space)
+                .map(|x| x.1)
+                .with_context(|| format!("No UCUM unit for {space}"))?;
+            let base = Dimensions::parse_native(code, UnitRegistry::builtin())?.units()[0];
+            factor *= unit.to_base(1.0) / base.to_base(1.0).powf(power);
+            add_atom(code, power, true, 0.0);
+        }
+    }
+
+    atoms.retain(|x| x.1.abs() >= POWER_EPSILON);
+    for (code, power, metric, exponent) in &mut atoms {
+        if (*power - power.round()).abs() >= POWER_EPSILON {
+            bail!("UCUM can not represent the non integer power {power} of `{code}`");
+        }
+        *power = power.round();
+
+        match prefix(*exponent, *power).filter(|_| *metric) {
+            Some(prefix) => *code = format!("{}{code}", prefix.0),
+            None => factor *= (10 as Num).powf(*exponent),
+        }
+        *exponent = 0.0;
+    }
+
+    // Write a remaining power of ten as a prefix if possible, like `km2` instead of `10*6.m2`.
+    let (digits, mut exponent) = split_factor(factor);
+    if digits == "1" {
+        let unprefixed = atoms
+            .iter_mut()
+            .filter(|x| x.2 && x.1 > 0.0 && UNIT_ATOMS.iter().any(|(code, ..)| *code == x.0));
+        for (code, power, ..) in unprefixed {
+            if let Some(prefix) = prefix(exponent as Num, *power).filter(|x| x.1 % 3 == 0) {
+                *code = format!("{}{code}", prefix.0);
+                exponent = 0;
+                break;
+            }
+        }
+    }
+
+    let mut out = match (digits.as_str(), exponent) {
+        ("1", 0) => String::new(),
+        (_, 0..=3) => format!("{digits}{}", "0".repeat(exponent as usize)),
+        ("1", _) => format!("10*{exponent}"),
+        _ => format!("{digits}.10*{exponent}"),
+    };
+
+    for (code, power, ..) in atoms.iter().filter(|x| x.1 > 0.0) {
+        if !out.is_empty() {
+            out.push('.');
+        }
+        out.push_str(&atom(code, *power));
+    }
+
+    for (code, power, ..) in atoms.iter().filter(|x| x.1 < 0.0) {
+        out.push('/');
+        out.push_str(&atom(code, -power));
+    }
+
+    if out.is_empty() {
+        out.push('1');
+    }
+
+    Ok(out)
+}
+
+/// Finds the prefix that scales an atom raised to `power` by `10^exponent`.
+fn prefix(exponent: Num, power: Num) -> Option<&'static (&'static str, i32)> {
+    let prefix = exponent / power;
+    PREFIXES
+        .iter()
+        .find(|x| prefix.fract() == 0.0 && x.1 == prefix as i32)
+}
+
+fn atom(code: &str, power: Num) -> String {
+    if power == 1.0 {
+        code.to_owned()
+    } else {
+        format!("{code}{power}")
+    }
+}
+
+/// Splits a factor into integer digits and a power of ten, like `0.3048` into `3048` and `-4`.
+fn split_factor(factor: Num) -> (String, i32) {
+    let scientific = format!("{factor:e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let exponent = exponent.parse::<i32>().unwrap() - (digits.len() as i32 - 1);
+    (digits, exponent)
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    index: usize,
+    registry: &'a UnitRegistry,
+}
+
+impl Parser<'_> {
+    fn term(&mut self) -> Result<Dimensions> {
+        let mut out = match self.peek() {
+            Some('/') => Dimensions::default(),
+            _ => self.component()?,
+        };
+
+        while let Some(op @ ('.' | '/')) = self.peek() {
+            self.index += 1;
+            let rhs = self.component()?;
+            out = if op == '.' { out * rhs } else { out / rhs };
+        }
+
+        Ok(out)
+    }
+
+    fn component(&mut self) -> Result<Dimensions> {
+        let out = match self.peek() {
+            Some('(') => {
+                self.index += 1;
+                let inner = self.term()?;
+                if self.peek() != Some(')') {
+                    bail!("Missing closing parenthesis in UCUM code");
+                }
+                self.index += 1;
+                inner
+            }
+            Some('{') => Dimensions::default(),
+            Some(_) => self.annotatable()?,
+            None => bail!("Unexpected end of UCUM code"),
+        };
+
+        if self.peek() == Some('{') {
+            self.annotation()?;
+        }
+
+        Ok(out)
+    }
+
+    fn annotation(&mut self) -> Result<()> {
+        match self.chars[self.index..].iter().position(|x| *x == '}') {
+            Some(end) => self.index += end + 1,
+            None => bail!("Unterminated annotation in UCUM code"),
+        }
+
+        Ok(())
+    }
+
+    /// A factor like `100`, or an atom with an optional prefix and power, like `cm2` or `[in_i]`.
+    fn annotatable(&mut self) -> Result<Dimensions> {
+        let start = self.index;
+        let mut depth = 0;
+        while let Some(chr) = self.peek() {
+            match chr {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '.' | '/' | '(' | ')' | '{' if depth == 0 => break,
+                _ => {}
+            }
+            self.index += 1;
+        }
+        let symbol = self.chars[start..self.index].iter().collect::<String>();
+
+        if symbol.chars().all(|x| x.is_ascii_digit()) {
+            return Ok(Dimensions::factor(symbol.parse()?));
+        }
+
+        let mut atom = symbol.trim_end_matches(|x: char| x.is_ascii_digit());
+        if atom.len() < symbol.len() {
+            atom = atom.strip_suffix(['+', '-']).unwrap_or(atom);
+        }
+        if atom.is_empty() {
+            bail!("Invalid UCUM unit `{symbol}`");
+        }
+
+        let dimensions = self.simple_unit(atom)?;
+        Ok(match &symbol[atom.len()..] {
+            "" => dimensions,
+            power => dimensions.powf(power.parse::<i32>()? as Num),
+        })
+    }
+
+    fn simple_unit(&self, code: &str) -> Result<Dimensions> {
+        if let Some(atom) = ATOMS.iter().find(|x| x.0 == code) {
+            return self.atom(atom);
+        }
+
+        for (prefix, power) in PREFIXES {
+            let Some(atom) = code
+                .strip_prefix(prefix)
+                .and_then(|code| ATOMS.iter().find(|x| x.0 == code && x.3))
+            else {
+                continue;
+            };
+
+            // Keep the prefix on the unit if possible, so it can be written back the same way.
+            let dimensions = self.atom(atom)?;
+            return Ok(match dimensions.units() {
+                [unit] if unit.power() == 1.0 => Dimensions::from_units(vec![Unit::new(
+                    unit.conversion(),
+                    1.0,
+                    unit.sci_exponent() + *power as Num,
+                )]),
+                _ => dimensions * Dimensions::factor((10 as Num).powi(*power)),
+            });
+        }
+
+        bail!("Unknown UCUM unit `{code}`")
+    }
+
+    fn atom(&self, &(code, unit, factor, _): &(&str, &str, Num, bool)) -> Result<Dimensions> {
+        let dimensions = Dimensions::parse_native(unit, self.registry)
+            .with_context(|| format!("UCUM unit `{code}` is not defined"))?;
+        Ok(if factor == 1.0 {
+            dimensions
+        } else {
+            dimensions * Dimensions::factor(factor)
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{format, parse, ATOMS};
+    use crate::{
+        dimension::Dimensions,
+        registry::{UnitRegistry, UnitSyntax},
+    };
+
+    fn convert(value: f64, from: &str, to: &str) -> f64 {
+        let registry = UnitRegistry::builtin();
+        let from = parse(from, registry).unwrap();
+        let to = Dimensions::parse_native(to, registry).unwrap();
+        from.convert(&to, value, false).unwrap()
+    }
+
+    #[test]
+    fn test_atoms() {
+        for (code, unit, ..) in ATOMS {
+            assert!(
+                Dimensions::parse_native(unit, UnitRegistry::builtin()).is_ok(),
+                "`{code}` uses the unknown unit `{unit}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_relative_eq!(convert(100.0, "mg/dL", "g/dm^3"), 1.0);
+        assert_relative_eq!(
+            convert(120.0, "mm[Hg]", "kPa"),
+            15.998_686_489_8,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(convert(1.0, "[in_i]", "cm"), 2.54);
+        assert_relative_eq!(convert(5.0, "10*3/uL", "dm^-3"), 5e9);
+        assert_relative_eq!(convert(50.0, "%", ""), 0.5);
+        assert_relative_eq!(convert(2.0, "{cells}/uL", "cm^-3"), 2000.0);
+        assert_relative_eq!(convert(1.0, "kg.m/s2", "N"), 1.0);
+        assert_relative_eq!(convert(1.0, "/min", "Hz"), 1.0 / 60.0);
+        assert_relative_eq!(convert(3.0, "m2", "ft^2"), 32.291_730_8, epsilon = 1e-6);
+        assert_relative_eq!(convert(1.0, "10*-3.L", "cm^3"), 1.0);
+
+        let registry = UnitRegistry::builtin();
+        assert!(parse("KG", registry).is_err());
+        assert!(parse("[in_i", registry).is_err());
+        assert!(parse("k[in_i]", registry).is_err());
+        assert!(parse("m{note", registry).is_err());
+
+        let mut registry = UnitRegistry::new();
+        registry.set_syntax(UnitSyntax::Ucum);
+        assert!(Dimensions::parse_with("kg.m/s2", &registry).is_ok());
+        assert!(Dimensions::parse_with("kg*m/s^2", &registry).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let registry = UnitRegistry::builtin();
+        let format = |x: &str| format(&parse(x, registry).unwrap()).unwrap();
+        let format_native =
+            |x: &str| super::format(&Dimensions::parse_native(x, registry).unwrap()).unwrap();
+
+        assert_eq!(format("kg.m/s2"), "kg.m/s2");
+        assert_eq!(format("[in_i]"), "[in_i]");
+        assert_eq!(format("km2"), "km2");
+        assert_eq!(format("10*3/uL"), "10*9/dm3");
+        assert_eq!(format("mg/dL"), "10.mg/dm3");
+        assert_eq!(format("{cells}"), "1");
+        assert_eq!(format_native("N"), "kg.m/s2");
+        assert_eq!(format_native("mph"), "[mi_i]/h");
+        #[cfg(feature = "wacky_units")]
+        assert_eq!(format_native("smoot"), "17.10*-1.m");
+        assert!(super::format(&Dimensions::parse_native("Hz^0.5", registry).unwrap()).is_err());
+
+        for code in ["mg/dL", "mm[Hg]", "10*3/uL", "[lb_av].[ft_i]/s2"] {
+            let dimensions = parse(code, registry).unwrap();
+            let formatted = parse(&format(code), registry).unwrap();
+            assert_relative_eq!(
+                dimensions.convert(&formatted, 1.0, false).unwrap(),
+                1.0,
+                epsilon = 1e-12
+            );
+        }
+    }
+}
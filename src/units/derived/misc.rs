@@ -22,6 +22,7 @@ impl_derived_units! {
         ],
         description = "The unit of frequency in the International System of Units. Equal to 1 cycle per second.",
         link = "https://en.wikipedia.org/wiki/Hertz",
+        aliases = ["Hz"],
         metric = true
     ],
     /// `kg^{-1}*m^{-2}*s^4*A^2`
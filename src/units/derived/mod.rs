@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use super::{Conversion, Space};
+use super::{Conversion, Space, Transform};
 use crate::dimension::Unit;
 
 pub mod electric_charge;
@@ -25,6 +25,7 @@ pub const DERIVED_UNITS: &[&[&DerivedConversion]] = &[
     voltage::UNITS,
 ];
 
+#[derive(Clone)]
 pub struct DerivedConversion {
     pub name: &'static str,
     pub description: Option<&'static str>,
@@ -32,6 +33,9 @@ pub struct DerivedConversion {
     pub expand: &'static [Unit],
     pub aliases: &'static [&'static str],
     pub metric: bool,
+    /// The path of the module the unit was defined in, like `unit_convert::units::derived::force`.
+    /// Units defined at runtime use a plain name instead.
+    pub module: &'static str,
 }
 
 #[inline]
@@ -45,6 +49,13 @@ pub fn get(s: &str) -> Option<&'static DerivedConversion> {
     })
 }
 
+impl DerivedConversion {
+    /// The name of the group the unit is listed under, the last part of its module path.
+    pub fn group(&self) -> &'static str {
+        self.module.rsplit("::").next().unwrap()
+    }
+}
+
 impl Debug for DerivedConversion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.name)
@@ -89,7 +100,8 @@ macro_rules! impl_derived_units {
                     link: None,
                     expand: &[],
                     aliases: &[$($($aliases),*)?],
-                    metric: false $(|| $metric)?
+                    metric: false $(|| $metric)?,
+                    module: module_path!()
                 }
             };
         )*
@@ -99,24 +111,22 @@ macro_rules! impl_derived_units {
 pub macro constant {
     ($conversion:literal, $exponent:expr) => {{
         #[allow(clippy::excessive_precision)]
-        Unit::new(
-            &Conversion {
-                name: "virtual-unit",
-                #[cfg(feature = "documentation")]
-                description: None,
-                #[cfg(feature = "documentation")]
-                link: None,
-                space: Space::Dynamic,
-                to_base: |x| x * $conversion,
-                from_base: |x| x / $conversion,
-                aliases: &[],
-                metric: false,
-                // TODO: Replace with special space?
-                special: true,
-            },
-            1.0,
-            $exponent,
-        )
+        const CONSTANT: Conversion = Conversion {
+            name: "virtual-unit",
+            #[cfg(feature = "documentation")]
+            description: None,
+            #[cfg(feature = "documentation")]
+            link: None,
+            space: Space::Dynamic,
+            transform: Transform::scale($conversion),
+            aliases: &[],
+            metric: false,
+            // TODO: Replace with special space?
+            special: true,
+            wacky: false,
+            delta: false,
+        };
+        Unit::new(&CONSTANT, 1.0, $exponent)
     }},
     ($conversion:literal) => {
         constant!($conversion, 0.0)
@@ -1,7 +1,8 @@
 use crate::{
     dimension::Unit,
     impl_derived_units, join_arrays,
-    units::{derived::constant, length, mass, time},
+    units::{derived::constant, length, mass, time, Conversion, Space, Transform},
+    Num,
 };
 
 const _PASCAL: [Unit; 3] = [
@@ -10,6 +11,23 @@ const _PASCAL: [Unit; 3] = [
     Unit::new(&time::SECOND, -2.0, 0.0),
 ];
 
+/// The standard atmosphere gauge pressures are relative to, in pascals.
+pub const STANDARD_ATMOSPHERE: Num = 101_325.0;
+
+/// Adds the atmosphere to a gauge pressure to get the absolute pressure.
+/// The offset is in the base units of pressure, `g/(m*s^2)`, so this has to come after the other units.
+pub const GAUGE: Conversion = Conversion {
+    special: true,
+    ..Conversion::new(
+        "gauge",
+        Space::Dynamic,
+        Transform::affine(1.0, STANDARD_ATMOSPHERE * 1e3),
+    )
+};
+
+const _PSI: [Unit; 4] = join_arrays!(_PASCAL, [constant!(6.894757, 3.0)]);
+const _BAR: [Unit; 4] = join_arrays!(_PASCAL, [constant!(100.0, 3.0)]);
+
 impl_derived_units! {
     /// `kg*m^{−1}*s^{−2}`
     PASCAL => [
@@ -21,17 +39,13 @@ impl_derived_units! {
     ],
     /// `6.894757 kPa`
     PSI => [
-        <| join_arrays!(_PASCAL, [
-            constant!(6.894757, 3.0)
-        ]),
+        <| _PSI,
         description = "A unit of measurement of pressure or of stress based on avoirdupois units. Equal to 6.894757 kPa.",
         link = "https://en.wikipedia.org/wiki/Pound_per_square_inch"
     ],
     /// `100 kPa`
     BAR => [
-        <| join_arrays!(_PASCAL, [
-            constant!(100.0, 3.0)
-        ]),
+        <| _BAR,
         description = "Exactly 100,000 Pa.",
         link = "https://en.wikipedia.org/wiki/Bar_(unit)",
         metric = true
@@ -51,5 +65,36 @@ impl_derived_units! {
         ]),
         description = "Exactly 1/760 of a standard atmosphere (101325 Pa). Thus one torr is exactly 101325/760 pascals (≈ 133.32 Pa).",
         link = "https://en.wikipedia.org/wiki/Torr"
+    ],
+    /// `psi`
+    PSIA => [
+        <| _PSI,
+        description = "Absolute pressure in psi, the same as psi. Written to tell it apart from psig.",
+        link = "https://en.wikipedia.org/wiki/Pound_per_square_inch"
+    ],
+    /// `psi + 1 atm`
+    PSIG => [
+        <| join_arrays!(_PSI, [Unit::new(&GAUGE, 1.0, 0.0)]),
+        description = "Gauge pressure in psi, relative to a standard atmosphere. 0 psig is 14.696 psia.",
+        link = "https://en.wikipedia.org/wiki/Pressure_measurement#Absolute"
+    ],
+    /// `bar`
+    BARA => [
+        <| _BAR,
+        description = "Absolute pressure in bar, the same as bar. Written to tell it apart from barg.",
+        link = "https://en.wikipedia.org/wiki/Bar_(unit)"
+    ],
+    /// `bar + 1 atm`
+    BARG => [
+        <| join_arrays!(_BAR, [Unit::new(&GAUGE, 1.0, 0.0)]),
+        description = "Gauge pressure in bar, relative to a standard atmosphere. 0 barg is 1.01325 bara.",
+        link = "https://en.wikipedia.org/wiki/Pressure_measurement#Absolute"
+    ],
+    /// `kPa + 1 atm`
+    KPAG => [
+        <| join_arrays!(_PASCAL, [constant!(1.0, 3.0), Unit::new(&GAUGE, 1.0, 0.0)]),
+        description = "Gauge pressure in kilopascals, relative to a standard atmosphere. 0 kPag is 101.325 kPa.",
+        link = "https://en.wikipedia.org/wiki/Pressure_measurement#Absolute",
+        aliases = ["kPag"]
     ]
 }
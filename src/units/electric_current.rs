@@ -3,8 +3,7 @@ use crate::impl_units;
 impl_units! {
     ELECTRIC_CURRENT => {
         AMPERE => [
-            <| |a| a,
-            |> |a| a,
+            scale = 1.0,
             description = "The unit of electric current in the International System of Units. Equal to 1 coulomb.",
             link = "https://en.wikipedia.org/wiki/Ampere",
             aliases = ["A", "amp"],
@@ -1,9 +1,6 @@
 use std::fmt::{Debug, Display};
 
-use crate::{
-    shorthand::{self, Shorthand},
-    Num,
-};
+use crate::{registry::UnitRegistry, shorthand::Shorthand, Num};
 
 use self::derived::DerivedConversion;
 
@@ -59,6 +56,24 @@ pub struct UnitSpace {
     pub units: &'static [&'static Conversion],
 }
 
+/// How a value in a unit is converted to and from the base unit of its space.
+#[derive(Debug, Clone, Copy)]
+pub enum Transform {
+    /// The base value is `(value - zero) * scale + offset`.
+    /// Only absolute temperatures and gauge pressures have an offset.
+    /// `zero` is the value that is `offset` in the base unit, like 32 °F is 273.15 K,
+    /// which keeps conversions between two offset scales from picking up rounding errors.
+    Linear { scale: Num, offset: Num, zero: Num },
+    /// Any other conversion, for units that are not a scale and offset of the base unit.
+    Function {
+        /// Converts a value in this unit to the unit space's base unit.
+        to_base: fn(Num) -> Num,
+        /// Converts a value in the unit space's base unit to this unit.
+        from_base: fn(Num) -> Num,
+    },
+}
+
+#[derive(Clone)]
 pub struct Conversion {
     /// Gets the name of the unit.
     pub name: &'static str,
@@ -70,10 +85,8 @@ pub struct Conversion {
     pub link: Option<&'static str>,
     /// Gets the unit space of the unit.
     pub space: Space,
-    /// Converts a value in this unit to the unit space's base unit.
-    pub to_base: fn(Num) -> Num,
-    /// Converts a value in the unit space's base unit to this unit.
-    pub from_base: fn(Num) -> Num,
+    /// Converts values between this unit and the unit space's base unit.
+    pub transform: Transform,
     /// Gets the aliases of the unit.
     pub aliases: &'static [&'static str],
     /// Checks if the unit is a metric unit.
@@ -81,6 +94,110 @@ pub struct Conversion {
     pub metric: bool,
     /// Checks if the unit is a special unit, which are virtual just used to hack in support for constants in derived units.
     pub special: bool,
+    /// Checks if the unit is only included with the `wacky_units` feature.
+    pub wacky: bool,
+    /// Checks if the unit is a temperature difference, like `ΔdegC`.
+    /// Differences are never converted with an offset, even on their own.
+    pub delta: bool,
+}
+
+impl Transform {
+    /// A transform that only multiplies by `scale`.
+    pub const fn scale(scale: Num) -> Self {
+        Self::affine(scale, 0.0)
+    }
+
+    /// A transform that multiplies by `scale` then adds `offset`.
+    pub const fn affine(scale: Num, offset: Num) -> Self {
+        Self::Linear {
+            scale,
+            offset,
+            zero: 0.0,
+        }
+    }
+
+    /// The scale and offset, as in `value * scale + offset`, if the transform is linear.
+    pub fn linear(&self) -> Option<(Num, Num)> {
+        match *self {
+            Self::Linear {
+                scale,
+                offset,
+                zero,
+            } => Some((scale, offset - zero * scale)),
+            Self::Function { .. } => None,
+        }
+    }
+
+    pub fn to_base(&self, value: Num) -> Num {
+        match *self {
+            Self::Linear {
+                scale,
+                offset,
+                zero,
+            } => (value - zero) * scale + offset,
+            Self::Function { to_base, .. } => to_base(value),
+        }
+    }
+
+    pub fn from_base(&self, value: Num) -> Num {
+        match *self {
+            Self::Linear {
+                scale,
+                offset,
+                zero,
+            } => (value - offset) / scale + zero,
+            Self::Function { from_base, .. } => from_base(value),
+        }
+    }
+
+    /// The transform from the base unit to this one.
+    pub fn inverse(&self) -> Self {
+        match *self {
+            Self::Linear {
+                scale,
+                offset,
+                zero,
+            } => Self::Linear {
+                scale: 1.0 / scale,
+                offset: zero,
+                zero: offset,
+            },
+            Self::Function { to_base, from_base } => Self::Function {
+                to_base: from_base,
+                from_base: to_base,
+            },
+        }
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Linear {
+                    scale: a,
+                    offset: b,
+                    zero: c,
+                },
+                Self::Linear {
+                    scale: d,
+                    offset: e,
+                    zero: f,
+                },
+            ) => a == d && b == e && c == f,
+            (
+                Self::Function {
+                    to_base: a,
+                    from_base: b,
+                },
+                Self::Function {
+                    to_base: c,
+                    from_base: d,
+                },
+            ) => std::ptr::fn_addr_eq(*a, *c) && std::ptr::fn_addr_eq(*b, *d),
+            _ => false,
+        }
+    }
 }
 
 impl UnitSpace {
@@ -90,6 +207,24 @@ impl UnitSpace {
 }
 
 impl Conversion {
+    /// Creates a unit without documentation, aliases or metric prefixes.
+    pub const fn new(name: &'static str, space: Space, transform: Transform) -> Self {
+        Self {
+            name,
+            #[cfg(feature = "documentation")]
+            description: None,
+            #[cfg(feature = "documentation")]
+            link: None,
+            space,
+            transform,
+            aliases: &[],
+            metric: false,
+            special: false,
+            wacky: false,
+            delta: false,
+        }
+    }
+
     /// Checks if the given name is the name or an alias of this unit.
     pub fn is_alias(&self, name: &str) -> bool {
         self.name == name.to_ascii_lowercase() || self.aliases.contains(&name)
@@ -115,12 +250,10 @@ impl ConversionType {
 }
 
 // todo: cleanup with unit::get
+/// Finds a built in unit by name or alias.
+/// Use [`UnitRegistry::find_unit`](crate::registry::UnitRegistry::find_unit) to include runtime defined units.
 pub fn find_unit(s: &str) -> Option<ConversionType> {
-    UNIT_SPACES
-        .iter()
-        .find_map(|space| space.get(s).map(ConversionType::Conversion))
-        .or_else(|| derived::get(s).map(ConversionType::DerivedConversion))
-        .or_else(|| shorthand::get(s).map(ConversionType::Shorthand))
+    UnitRegistry::builtin().find_unit(s)
 }
 
 impl Display for UnitSpace {
@@ -176,18 +309,32 @@ impl PartialEq for ConversionType {
     }
 }
 
+/// Checks if a unit's attribute is `cfg(feature = "wacky_units")`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! is_wacky {
+    (cfg(feature = "wacky_units")) => {
+        true
+    };
+    ($($meta:tt)*) => {
+        false
+    };
+}
+
 #[macro_export]
 macro_rules! impl_units {
     ($space:ident => {
         $(
-            $(#[$meta:meta])?
+            $(#[$($meta:tt)*])?
             $struct:ident => [
-                <| $to_base:expr,
-                |> $from_base:expr
+                scale = $scale:expr
+                $(, offset = $offset:expr)?
+                $(, zero = $zero:expr)?
                 $(, description = $description:expr)?
                 $(, link = $link:expr)?
                 $(, aliases = [$($aliases:expr),*])?
                 $(, metric = $metric:expr)?
+                $(, delta = $delta:expr)?
             ]
         ),*
     }) => {
@@ -198,11 +345,11 @@ macro_rules! impl_units {
             name: lower_strify!($space),
             space: paste::expr! { Space::[< $space:camel >] },
             #[allow(unused_doc_comments)]
-            units: &[$($(#[$meta])?&$struct,)*]
+            units: &[$($(#[$($meta)*])?&$struct,)*]
         };
 
         $(
-            $(#[$meta])?
+            $(#[$($meta)*])?
             #[allow(clippy::excessive_precision)]
             pub const $struct: Conversion = Conversion {
                 $(#[cfg(feature = "documentation")]
@@ -216,11 +363,16 @@ macro_rules! impl_units {
                     #[cfg(feature = "documentation")]
                     link: None,
                     space: paste::expr! { Space::[< $space:camel >] },
-                    to_base: $to_base,
-                    from_base: $from_base,
+                    transform: $crate::units::Transform::Linear {
+                        scale: $scale,
+                        offset: 0.0 $(+ $offset)?,
+                        zero: 0.0 $(+ $zero)?,
+                    },
                     aliases: &[$($($aliases),*)?],
                     metric: false $(|| $metric)?,
-                    special: false
+                    special: false,
+                    wacky: $crate::is_wacky!($($($meta)*)?),
+                    delta: false $(|| $delta)?
                 }
             };
         )*
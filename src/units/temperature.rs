@@ -3,32 +3,45 @@ use crate::impl_units;
 impl_units! {
     TEMPERATURE => {
         KELVIN => [
-            <| |k| k,
-            |> |k| k,
+            scale = 1.0,
             description = "An absolute scale, defined such that 0 K is absolute zero and a change of thermodynamic temperature T by 1 kelvin corresponds to a change of thermal energy kT by 1.380649*10^{-23} J",
             link = "https://en.wikipedia.org/wiki/Kelvin",
-            aliases = ["k", "degk"]
+            aliases = ["K", "k", "degk"]
         ],
         CELSIUS => [
-            <| |c| c + 273.15,
-            |> |k| k - 273.15,
+            scale = 1.0,
+            offset = 273.15,
             description = "(x + 273.15) K",
             link = "https://en.wikipedia.org/wiki/Celsius",
-            aliases = ["c", "degc"]
+            aliases = ["c", "degc", "degC", "°C"]
         ],
         FAHRENHEIT => [
-            <| |f| (f - 32.0) * 5.0 / 9.0 + 273.15,
-            |> |k| (k - 273.15) * 9.0 / 5.0 + 32.0,
+            scale = 5.0 / 9.0,
+            offset = 273.15,
+            zero = 32.0,
             description = "5/9(x + 459.67) K",
             link = "https://en.wikipedia.org/wiki/Fahrenheit",
-            aliases = ["f", "degf"]
+            aliases = ["f", "degf", "degF", "°F"]
         ],
         RANKINE => [
-            <| |r| r * 5.0 / 9.0,
-            |> |k| k * 9.0 / 5.0,
+            scale = 5.0 / 9.0,
             description = "5/9 x K",
             link = "https://en.wikipedia.org/wiki/Rankine_scale",
             aliases = ["r", "degr"]
+        ],
+        DELTA_CELSIUS => [
+            scale = 1.0,
+            description = "A difference of one degree Celsius, equal to a difference of 1 K.",
+            link = "https://en.wikipedia.org/wiki/Celsius#Temperatures_and_intervals",
+            aliases = ["ΔdegC", "Δc", "delta_degC", "delta_c", "delta_celsius"],
+            delta = true
+        ],
+        DELTA_FAHRENHEIT => [
+            scale = 5.0 / 9.0,
+            description = "A difference of one degree Fahrenheit, equal to a difference of 5/9 K.",
+            link = "https://en.wikipedia.org/wiki/Fahrenheit",
+            aliases = ["ΔdegF", "Δf", "delta_degF", "delta_f", "delta_fahrenheit"],
+            delta = true
         ]
     }
 }
@@ -1,7 +1,8 @@
-#![feature(iter_intersperse)]
-
 use anyhow::bail;
-use unit_convert::{self, dimension::Dimensions, input};
+use unit_convert::{
+    self,
+    input::{self, target::Target},
+};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use std::str::FromStr;
@@ -18,21 +19,15 @@ pub fn convert(input: &str) -> Result<f64, String> {
     fn inner(input: &str) -> anyhow::Result<f64> {
         let inp = input::Input::from_str(input)?;
 
-        let from_dim = Dimensions::from_str(&inp.from_unit)?;
-        let to_dim = Dimensions::from_str(&inp.to_unit)?;
-
-        if from_dim != to_dim {
-            bail!(
-                "Unit dimensions do not match. ({:#} vs {:#})",
-                from_dim.simplify(),
-                to_dim.simplify()
-            );
-        }
+        let [Target::Unit(..)] = &inp.to[..] else {
+            bail!("Compound and multiple targets can not be converted to a single number.");
+        };
 
-        from_dim.convert(&to_dim, inp.value, false)
+        let parts = inp.convert(false)?.remove(0)?;
+        Ok(parts[0].value)
     }
 
-    inner(input).map_err(|x| x.to_string())
+    inner(input).map_err(|x| format!("{x:#}"))
 }
 
 // Note to self: build with `wasm-pack build --target web`
@@ -1,66 +1,34 @@
-use serde::Serialize;
-use unit_convert::units::{derived::DERIVED_UNITS, UNIT_SPACES};
+use unit_convert::{
+    catalog::{Catalog, UnitKind},
+    registry::UnitRegistry,
+};
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
-#[derive(Serialize)]
-pub struct Unit {
-    name: String,
-    description: Option<String>,
-    link: Option<String>,
-    space: String,
-    aliases: Vec<String>,
-    metric: bool,
+/// Every built in unit, see [`unit_convert::catalog::CatalogUnit`].
+#[wasm_bindgen]
+pub fn get_units() -> Result<Vec<JsValue>, String> {
+    units_of_kind(UnitKind::Unit)
 }
 
+/// Every built in derived unit, with what it expands to in its `definition`.
 #[wasm_bindgen]
-pub fn get_units() -> Vec<JsValue> {
-    UNIT_SPACES
-        .iter()
-        .flat_map(|x| x.units.iter())
-        .map(|unit| Unit {
-            name: unit.name.to_owned(),
-            description: unit.description.map(|x| x.to_owned()),
-            link: unit.link.map(|x| x.to_owned()),
-            space: unit.space.to_string(),
-            aliases: unit.aliases.iter().map(|&x| x.to_owned()).collect(),
-            metric: unit.metric,
-        })
-        .map(|x| serde_wasm_bindgen::to_value(&x).unwrap())
-        .collect()
+pub fn get_derived_units() -> Result<Vec<JsValue>, String> {
+    units_of_kind(UnitKind::Derived)
 }
 
-#[derive(Serialize)]
-pub struct DerivedUnit {
-    name: String,
-    description: Option<String>,
-    link: Option<String>,
-    aliases: Vec<String>,
-    metric: bool,
-    derived_from: String,
+/// Every unit, derived unit, shorthand and prefix, see [`Catalog`].
+#[wasm_bindgen]
+pub fn get_catalog() -> Result<JsValue, String> {
+    Ok(serde_wasm_bindgen::to_value(&catalog()?).unwrap())
 }
 
-#[wasm_bindgen]
-pub fn get_derived_units() -> Vec<JsValue> {
-    DERIVED_UNITS
-        .iter()
-        .flat_map(|x| x.iter())
-        .map(|x| DerivedUnit {
-            name: x.name.to_owned(),
-            description: x.description.map(|x| x.to_owned()),
-            link: x.link.map(|x| x.to_owned()),
-            aliases: x.aliases.iter().map(|&x| x.to_owned()).collect(),
-            metric: x.metric,
-            derived_from: stringify_units(&x.expand),
-        })
-        .map(|x| serde_wasm_bindgen::to_value(&x).unwrap())
-        .collect()
+fn units_of_kind(kind: UnitKind) -> Result<Vec<JsValue>, String> {
+    Ok((catalog()?.units.iter())
+        .filter(|x| x.kind == kind)
+        .map(|x| serde_wasm_bindgen::to_value(x).unwrap())
+        .collect())
 }
 
-fn stringify_units(units: &[unit_convert::dimension::Unit]) -> String {
-    units
-        .iter()
-        .filter(|x| !x.is_special())
-        .map(|x| x.to_string())
-        .intersperse(" ".to_owned())
-        .collect()
+fn catalog() -> Result<Catalog, String> {
+    Catalog::new(UnitRegistry::builtin()).map_err(|x| format!("{x:#}"))
 }